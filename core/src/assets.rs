@@ -0,0 +1,92 @@
+//! Rewrite relative URLs of images and other files referenced by docs.
+
+use std::path::{Component, Path, PathBuf};
+
+use crate::markdown;
+
+/// A URL, such as a repository’s raw file URL, that serves files in a root
+/// directory.
+///
+/// Docs that refer to files with relative URLs, e.g. `![](diagram.png)`, break
+/// when they’re included in another module or displayed on docs.rs. This
+/// rewrites those URLs to be relative to the base URL instead.
+#[derive(Clone, Debug)]
+pub struct AssetUrls {
+    /// The directory that `url` serves, usually the crate root.
+    root: PathBuf,
+
+    /// The URL of `root`, ending with `/`.
+    url: String,
+}
+
+impl AssetUrls {
+    /// Create asset URLs that serve the files in `root` from `url`.
+    #[must_use]
+    pub fn new(root: PathBuf, url: &str) -> Self {
+        let url = if url.ends_with('/') {
+            url.to_owned()
+        } else {
+            format!("{url}/")
+        };
+        Self { root, url }
+    }
+
+    /// Rewrite relative URLs in `docs`, which were read from `path`.
+    ///
+    /// Only URLs of files that exist within the root directory are rewritten,
+    /// so intra-doc links, absolute URLs, and links to `rustdoc` pages are
+    /// left alone.
+    #[must_use]
+    pub fn rewrite(&self, path: &Path, docs: &str) -> String {
+        let (Ok(root), Some(dir)) = (self.root.canonicalize(), path.parent())
+        else {
+            return docs.to_owned();
+        };
+        markdown::rewrite_urls(docs, |url| {
+            if url.starts_with(['#', '/', '<']) || url.contains(':') {
+                return None;
+            }
+            let (file, fragment) =
+                url.split_at(url.find(['#', '?']).unwrap_or(url.len()));
+            let target = dir.join(file).canonicalize().ok()?;
+            let relative = target.strip_prefix(&root).ok()?;
+            let relative = relative
+                .components()
+                .filter_map(|component| match component {
+                    Component::Normal(name) => Some(name.to_string_lossy()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join("/");
+            Some(format!("{}{relative}{fragment}", self.url))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture;
+    use assert2::assert;
+
+    #[test]
+    fn rewrite_existing_files() {
+        let assets = AssetUrls::new(fixture(""), "https://example.com/raw");
+        assert!(
+            assets.rewrite(
+                &fixture("assets/pictures.rs"),
+                " ![a](diagram.svg) [b](../assets/diagram.svg#a) \
+                [c](missing.png) [d](crate::Foo) [e](/top.png)",
+            ) == " ![a](https://example.com/raw/assets/diagram.svg) \
+                [b](https://example.com/raw/assets/diagram.svg#a) \
+                [c](missing.png) [d](crate::Foo) [e](/top.png)"
+        );
+    }
+
+    #[test]
+    fn outside_root() {
+        let assets = AssetUrls::new(fixture("tree"), "https://example.com/");
+        let docs = " ![a](diagram.svg)";
+        assert!(assets.rewrite(&fixture("assets/pictures.rs"), docs) == docs);
+    }
+}
@@ -0,0 +1,201 @@
+//! Cache docs extracted from files between calls.
+//!
+//! The compiler loads a proc macro once per crate, so statics persist between
+//! macro invocations. Large crates often include the same file from many
+//! places, so this avoids parsing it each time.
+//!
+//! Entries are keyed by the file’s modification time, so that long-running
+//! processes like IDE proc macro servers notice when a file changes. The
+//! modification times of files included with `include_str!()` are checked
+//! too, since their contents are part of the cached docs.
+
+use crate::{
+    Blocks, DocExtractor, DocStyle, FileDocs, Hidden, record_read_path,
+};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex, PoisonError};
+use std::time::SystemTime;
+
+/// Extracted docs, keyed by file and the options used to extract them.
+static DOCS: LazyLock<Mutex<HashMap<DocsKey, Entry>>> =
+    LazyLock::new(Mutex::default);
+
+/// Cached docs, and the files they include.
+#[derive(Clone, Debug)]
+struct Entry {
+    /// The docs, or `None` if the file is hidden and hidden files are skipped.
+    docs: Option<FileDocs>,
+
+    /// Files included by the docs, with their modification times.
+    included: Vec<(PathBuf, SystemTime)>,
+}
+
+/// Identifies the docs extracted from a particular version of a file.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DocsKey {
+    /// Path to the file.
+    path: PathBuf,
+
+    /// When the file was last modified.
+    modified: SystemTime,
+
+    /// Which blocks of docs were extracted.
+    blocks: Blocks,
+
+    /// Whether docs were extracted from anywhere in the file.
+    anywhere: bool,
+
+    /// What to do with hidden files.
+    hidden: Hidden,
+
+    /// Whether docs were read from files that couldn’t be parsed.
+    lenient: bool,
+
+    /// Which styles of docs were read.
+    styles: Vec<DocStyle>,
+
+    /// The inline module whose docs were read, if any.
+    module: Option<String>,
+
+    /// Whether links to public items were found.
+    link_items: bool,
+}
+
+impl DocsKey {
+    /// Create a key for the docs extracted from `path` by `extractor`.
+    ///
+    /// Returns `None` if the file’s modification time isn’t available, in
+    /// which case its docs shouldn’t be cached.
+    pub fn new(path: &Path, extractor: &DocExtractor) -> Option<Self> {
+        Some(Self {
+            path: path.to_owned(),
+            modified: modified(path)?,
+            blocks: extractor.blocks,
+            anywhere: extractor.anywhere,
+            hidden: extractor.hidden,
+            lenient: extractor.lenient,
+            styles: extractor.styles.clone(),
+            module: extractor.module.clone(),
+            link_items: extractor.link_items,
+        })
+    }
+}
+
+/// Get when the file at `path` was last modified, if that’s available.
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+/// Get cached docs.
+///
+/// Returns `None` if nothing is cached or an included file has changed, or
+/// `Some(None)` if the file was skipped because it’s hidden. The paths of the
+/// file and the files it includes are recorded as if they had been read.
+#[expect(
+    clippy::option_option,
+    reason = "the inner `Option` is the cached value"
+)]
+pub fn get(key: &DocsKey) -> Option<Option<FileDocs>> {
+    let entry = DOCS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(key)
+        .cloned()?;
+    let unchanged = entry
+        .included
+        .iter()
+        .all(|(path, time)| modified(path) == Some(*time));
+    if !unchanged {
+        return None;
+    }
+    record_read_path(&key.path);
+    for (path, _) in &entry.included {
+        record_read_path(path);
+    }
+    Some(entry.docs)
+}
+
+/// Cache docs that include the files at `included`.
+///
+/// Nothing is cached if the modification time of an included file isn’t
+/// available.
+pub fn insert(key: DocsKey, docs: Option<FileDocs>, included: &[PathBuf]) {
+    let Some(included) = included
+        .iter()
+        .map(|path| Some((path.clone(), modified(path)?)))
+        .collect()
+    else {
+        return;
+    };
+    DOCS.lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(key, Entry { docs, included });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture;
+    use assert2::assert;
+
+    #[test]
+    fn round_trip() {
+        let key =
+            DocsKey::new(&fixture("tree/trunk.rs"), &DocExtractor::default())
+                .unwrap();
+        let docs = FileDocs { docs: "Docs".to_owned(), item_links: Vec::new() };
+        insert(key.clone(), Some(docs), &[]);
+        assert!(
+            get(&key).flatten().map(|docs| docs.docs)
+                == Some("Docs".to_owned())
+        );
+    }
+
+    #[test]
+    fn options_in_key() {
+        let path = fixture("tree/trunk.rs");
+        let all = DocsKey::new(&path, &DocExtractor::default());
+        let first = DocsKey::new(
+            &path,
+            &DocExtractor { blocks: Blocks::First, ..DocExtractor::default() },
+        );
+        assert!(all != first);
+    }
+
+    #[test]
+    fn included_file_changed() {
+        let dir = std::env::temp_dir()
+            .join(format!("read-doc-cache-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("including.rs");
+        let included = dir.join("included.md");
+        fs::write(&path, "#![doc = include_str!(\"included.md\")]\n").unwrap();
+        fs::write(&included, "Old docs.").unwrap();
+        let extractor = DocExtractor::default();
+        assert!(extractor.read_docs(&path).unwrap().unwrap() == "Old docs.");
+
+        fs::write(&included, "New docs.").unwrap();
+        // Ensure the time changes even if the filesystem’s clock is coarse.
+        let later = SystemTime::now()
+            .checked_add(std::time::Duration::from_secs(10))
+            .unwrap();
+        fs::File::options()
+            .write(true)
+            .open(&included)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        assert!(extractor.read_docs(&path).unwrap().unwrap() == "New docs.");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn missing_file() {
+        assert!(
+            DocsKey::new(&fixture("nonesuch.rs"), &DocExtractor::default())
+                == None
+        );
+    }
+}
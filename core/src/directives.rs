@@ -0,0 +1,322 @@
+//! Directives in a file’s docs that control how they are included.
+//!
+//! Directives are HTML comments, so they don’t show up in the file’s own
+//! documentation:
+//!
+//! ```text
+//! //! <!-- read-doc: title=Apples, shift_headings=1 -->
+//! ```
+
+use crate::markdown::{Fences, LineKind};
+use std::ops::Range;
+
+/// The prefix that marks an HTML comment as a directive.
+const PREFIX: &str = "read-doc:";
+
+/// Directives found in a file’s docs.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Directives {
+    /// Don’t include this file’s docs at all.
+    pub skip: bool,
+
+    /// Title to use for this file’s docs.
+    pub title: Option<String>,
+
+    /// How many levels to shift headings by, overriding the option passed to
+    /// the macro.
+    pub shift_headings: Option<usize>,
+
+    /// Put this file’s docs in a collapsed `<details>` element.
+    pub collapse: bool,
+}
+
+impl Directives {
+    /// Parse the contents of a directive comment and update `self`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a directive is unknown or has an invalid value.
+    fn parse(&mut self, directives: &str) -> Result<(), String> {
+        for directive in directives.split(',').map(str::trim) {
+            let (name, value) = match directive.split_once('=') {
+                Some((name, value)) => (name.trim(), Some(value.trim())),
+                None => (directive, None),
+            };
+            match (name, value) {
+                ("collapse", None) => self.collapse = true,
+                ("skip", None) => self.skip = true,
+                ("title", Some(value)) => self.title = Some(value.to_owned()),
+                ("shift_headings", Some(value)) => {
+                    self.shift_headings =
+                        Some(value.parse().map_err(|_| {
+                            format!(
+                                "Invalid shift_headings directive: {value:?}"
+                            )
+                        })?);
+                }
+                _ => {
+                    return Err(format!(
+                        "Unknown read-doc directive: {directive:?}"
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Find and remove directives from `docs`.
+///
+/// Directives must be on a line by themselves outside of code blocks. Blank
+/// lines following a directive are also removed.
+///
+/// Lines between `audience=name` and `/audience` directives are only kept if
+/// `audience` is `None` or `Some(name)`.
+///
+/// If `range` is set, only those lines of `docs` are kept, counting from 1.
+/// Directives and audience regions outside of the range still apply. If the
+/// range ends inside a fenced code block, the block is closed so that it
+/// doesn’t swallow whatever follows the docs.
+///
+/// # Errors
+///
+/// Returns an error if a directive is unknown or has an invalid value, or if
+/// audience regions are nested or unbalanced.
+pub fn take(
+    docs: &str,
+    audience: Option<&str>,
+    range: Option<&Range<usize>>,
+) -> Result<(String, Directives), String> {
+    let mut directives = Directives::default();
+    let mut lines = Vec::new();
+    let mut after_directive = false;
+    let mut region = None;
+    let mut fences = Fences::default();
+    let mut closing = None;
+    for (number, line) in (1..).zip(docs.split('\n')) {
+        let kind = fences.classify(line);
+        if range.is_some_and(|range| number < range.end) {
+            closing = fences.closing();
+        }
+        if kind == LineKind::Text
+            && let Some(contents) = parse_comment(line)
+        {
+            if contents == "/audience" {
+                if region.take().is_none() {
+                    return Err(format!(
+                        "Unbalanced read-doc directive: {contents:?}"
+                    ));
+                }
+            } else if let Some(name) = audience_start(contents)? {
+                if region.replace(name).is_some() {
+                    return Err(format!(
+                        "Unbalanced read-doc directive: {contents:?}"
+                    ));
+                }
+            } else {
+                directives.parse(contents)?;
+            }
+            after_directive = true;
+        } else if region.is_some_and(|name| audience.is_some_and(|a| a != name))
+        {
+            // Not for this audience.
+        } else if !(after_directive && line.trim().is_empty()) {
+            if range.is_none_or(|range| range.contains(&number)) {
+                lines.push(line);
+            }
+            after_directive = false;
+        }
+    }
+    if region.is_some() {
+        return Err("Missing read-doc directive: \"/audience\"".to_owned());
+    }
+    lines.extend(closing.as_deref());
+    Ok((lines.join("\n"), directives))
+}
+
+/// Get the audience from a directive like `audience=name` that starts an
+/// audience region.
+///
+/// # Errors
+///
+/// Returns an error if the audience is empty or followed by other directives,
+/// since it must be in its own comment.
+fn audience_start(contents: &str) -> Result<Option<&str>, String> {
+    let Some((name, value)) = contents.split_once('=') else {
+        return Ok(None);
+    };
+    if name.trim() != "audience" {
+        return Ok(None);
+    }
+    let value = value.trim();
+    if value.is_empty() || value.contains(',') {
+        Err(format!("Invalid audience directive: {contents:?}"))
+    } else {
+        Ok(Some(value))
+    }
+}
+
+/// Get the contents of a directive comment, if `line` is one.
+fn parse_comment(line: &str) -> Option<&str> {
+    line.trim()
+        .strip_prefix("<!--")?
+        .strip_suffix("-->")?
+        .trim()
+        .strip_prefix(PREFIX)
+        .map(str::trim)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::assert;
+
+    #[test]
+    fn no_directives() {
+        assert!(
+            take(" # Title\n\n Text", None, None).unwrap()
+                == (" # Title\n\n Text".to_owned(), Directives::default())
+        );
+    }
+
+    #[test]
+    fn skip() {
+        let (docs, directives) =
+            take(" <!-- read-doc: skip -->\n\n # Title", None, None).unwrap();
+        assert!(docs == " # Title");
+        assert!(directives.skip);
+    }
+
+    #[test]
+    fn multiple_directives() {
+        let (docs, directives) = take(
+            " # Title\n\n <!-- read-doc: title = Apples, shift_headings=2 -->\n\n Text",
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(docs == " # Title\n\n Text");
+        assert!(
+            directives
+                == Directives {
+                    skip: false,
+                    title: Some("Apples".to_owned()),
+                    shift_headings: Some(2),
+                    collapse: false,
+                }
+        );
+    }
+
+    #[test]
+    fn collapse() {
+        let (docs, directives) =
+            take(" <!-- read-doc: collapse -->\n # Title", None, None).unwrap();
+        assert!(docs == " # Title");
+        assert!(directives.collapse);
+    }
+
+    #[test]
+    fn other_comments_ignored() {
+        let (docs, directives) = take(" <!-- skip -->", None, None).unwrap();
+        assert!(docs == " <!-- skip -->");
+        assert!(directives == Directives::default());
+    }
+
+    #[test]
+    fn code_blocks_ignored() {
+        let docs = " ```text\n <!-- read-doc: skip -->\n ```";
+        assert!(
+            take(docs, None, None).unwrap()
+                == (docs.to_owned(), Directives::default())
+        );
+    }
+
+    #[test]
+    fn unknown_directive() {
+        assert!(
+            take(" <!-- read-doc: nonesuch -->", None, None).unwrap_err()
+                == r#"Unknown read-doc directive: "nonesuch""#
+        );
+    }
+
+    #[test]
+    fn invalid_shift() {
+        assert!(
+            let Err(_) = take(" <!-- read-doc: shift_headings=x -->", None, None)
+        );
+    }
+
+    #[test]
+    fn audience_regions() {
+        let docs = " A\n\n <!-- read-doc: audience=internal -->\n B\n \
+            <!-- read-doc: /audience -->\n\n C";
+        assert!(take(docs, None, None).unwrap().0 == " A\n\n B\n C");
+        assert!(
+            take(docs, Some("internal"), None).unwrap().0 == " A\n\n B\n C"
+        );
+        assert!(take(docs, Some("public"), None).unwrap().0 == " A\n\n C");
+    }
+
+    #[test]
+    fn unbalanced_audience() {
+        assert!(let Err(_) = take(" <!-- read-doc: /audience -->", None, None));
+        assert!(let Err(_) = take(" <!-- read-doc: audience=a -->", None, None));
+        assert!(
+            let Err(_) = take(
+                " <!-- read-doc: audience=a -->\n \
+                <!-- read-doc: audience=b -->",
+                None,
+                None,
+            )
+        );
+    }
+
+    #[test]
+    fn audience_extra_arguments() {
+        assert!(
+            take(" <!-- read-doc: audience=a, title=b -->", None, None)
+                .unwrap_err()
+                == r#"Invalid audience directive: "audience=a, title=b""#
+        );
+        assert!(let Err(_) = take(" <!-- read-doc: audience= -->", None, None));
+    }
+
+    #[test]
+    fn line_range_cuts_audience() {
+        let docs = " A\n <!-- read-doc: audience=internal -->\n B\n \
+            <!-- read-doc: /audience -->\n C";
+        assert!(take(docs, Some("public"), Some(&(1..4))).unwrap().0 == " A");
+        assert!(
+            take(docs, Some("internal"), Some(&(3..6))).unwrap().0 == " B\n C"
+        );
+    }
+
+    #[test]
+    fn line_range() {
+        let docs = " a\n b\n c\n d";
+        assert!(take(docs, None, Some(&(2..4))).unwrap().0 == " b\n c");
+        assert!(
+            take(docs, None, Some(&(2..usize::MAX))).unwrap().0 == " b\n c\n d"
+        );
+        assert!(take(" a", None, Some(&(3..5))).unwrap().0.is_empty());
+    }
+
+    #[test]
+    fn line_range_closes_fence() {
+        assert!(
+            take(" a\n ````rust\n b\n c\n ````\n d", None, Some(&(1..4)))
+                .unwrap()
+                .0
+                == " a\n ````rust\n b\n````"
+        );
+    }
+
+    #[test]
+    fn line_range_directives() {
+        let (docs, directives) =
+            take(" <!-- read-doc: skip -->\n a\n b", None, Some(&(3..4)))
+                .unwrap();
+        assert!(docs == " b");
+        assert!(directives.skip);
+    }
+}
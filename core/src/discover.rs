@@ -0,0 +1,397 @@
+//! Find module source files by following `mod` declarations.
+
+use std::path::{Path, PathBuf};
+use syn::ext::IdentExt;
+
+/// File names that are `mod.rs`-style module files.
+const MOD_RS_NAMES: [&str; 3] = ["mod.rs", "lib.rs", "main.rs"];
+
+/// Directories within a crate that glob matches are expected to be in.
+pub const SOURCE_DIRS: [&str; 4] = ["src", "docs", "examples", "tests"];
+
+/// A module source file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ModuleFile {
+    /// Path to the source file.
+    pub path: PathBuf,
+
+    /// Whether this is a `mod.rs`-style file, i.e. its submodules are found
+    /// in the same directory rather than in a directory named after it.
+    pub is_mod_rs: bool,
+}
+
+impl ModuleFile {
+    /// Create a `ModuleFile` for the file at `path`.
+    ///
+    /// `mod.rs`, `lib.rs`, and `main.rs` are assumed to be `mod.rs`-style
+    /// files.
+    #[must_use]
+    pub fn new(path: PathBuf) -> Self {
+        let is_mod_rs = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| MOD_RS_NAMES.contains(&name));
+        Self { path, is_mod_rs }
+    }
+
+    /// Get the directory in which this module’s submodules are found.
+    fn submodule_dir(&self) -> Option<PathBuf> {
+        let parent = self.path.parent()?;
+        if self.is_mod_rs {
+            Some(parent.to_path_buf())
+        } else {
+            Some(parent.join(self.path.file_stem()?))
+        }
+    }
+}
+
+/// Does `path` contain glob wildcards?
+#[must_use]
+pub fn is_glob(path: &str) -> bool {
+    path.contains(['*', '?', '['])
+}
+
+/// Find files matching a glob `pattern` relative to `base`.
+///
+/// Matches are sorted by comparing each component of their paths byte by byte,
+/// so the order doesn’t depend on the filesystem or the platform’s path
+/// separator. `exclude` is skipped if it matches, which prevents a file from
+/// including its own docs. Hidden files, such as editor lock files, are never
+/// matched.
+///
+/// # Errors
+///
+/// Returns an error if the pattern is invalid, a directory couldn’t be read,
+/// or nothing matched.
+pub fn glob(
+    base: &Path,
+    pattern: &str,
+    exclude: Option<&Path>,
+) -> Result<Vec<PathBuf>, String> {
+    let full = Path::new(&glob::Pattern::escape(&base.to_string_lossy()))
+        .join(pattern);
+    let options = glob::MatchOptions {
+        require_literal_leading_dot: true,
+        ..glob::MatchOptions::new()
+    };
+    let mut paths = glob::glob_with(&full.to_string_lossy(), options)
+        .map_err(|error| format!("Invalid glob {pattern:?}: {error}"))?
+        .filter(|result| {
+            result.as_ref().map_or(true, |path| {
+                path.is_file() && Some(path.as_path()) != exclude
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| error.to_string())?;
+
+    if paths.is_empty() {
+        return Err(format!("No files match {full:?}"));
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+/// Is `path` outside of the [`SOURCE_DIRS`] within `crate_dir`?
+///
+/// Both paths are canonicalized first, so `..` components and symlinks are
+/// resolved. Paths that can’t be canonicalized are considered outside.
+#[must_use]
+pub fn is_outside_source_dirs(crate_dir: &Path, path: &Path) -> bool {
+    let (Ok(crate_dir), Ok(path)) =
+        (crate_dir.canonicalize(), path.canonicalize())
+    else {
+        return true;
+    };
+    !SOURCE_DIRS
+        .iter()
+        .any(|dir| path.starts_with(crate_dir.join(dir)))
+}
+
+/// Find the root module file for a directory.
+///
+/// This looks for `mod.rs`, `lib.rs`, and `main.rs` within the directory, then
+/// for a file named after the directory next to it (e.g. `fruit.rs` for
+/// `fruit/`).
+///
+/// # Errors
+///
+/// Returns an error if no root module file could be found, or if both
+/// `mod.rs` and a file named after the directory exist, since `rustc` would
+/// reject the module as ambiguous.
+pub fn root_module(dir: &Path) -> Result<ModuleFile, String> {
+    let sibling = dir.file_name().map(|name| {
+        let mut file_name = name.to_os_string();
+        file_name.push(".rs");
+        dir.with_file_name(file_name)
+    });
+    let sibling = sibling.filter(|path| path.is_file());
+
+    for name in MOD_RS_NAMES {
+        let path = dir.join(name);
+        if path.is_file() {
+            if let Some(sibling) = sibling.filter(|_| name == "mod.rs") {
+                return Err(format!(
+                    "Module source file found at both {sibling:?} and {path:?}"
+                ));
+            }
+            return Ok(ModuleFile { path, is_mod_rs: true });
+        }
+    }
+
+    if let Some(path) = sibling {
+        return Ok(ModuleFile { path, is_mod_rs: false });
+    }
+
+    Err(format!(
+        "Could not find mod.rs, lib.rs, or main.rs in {dir:?}"
+    ))
+}
+
+/// Find all modules declared in `module`, recursively, with their paths.
+///
+/// Paths are relative to `module`, e.g. `trunk::branch`. Modules are returned
+/// in declaration order, with each module immediately followed by its own
+/// submodules. `module` itself is not included.
+///
+/// # Errors
+///
+/// Returns an error if a file could not be read or parsed, or if a declared
+/// module has no source file.
+pub fn module_tree(
+    module: &ModuleFile,
+) -> Result<Vec<(String, ModuleFile)>, String> {
+    let mut modules = Vec::new();
+    for (name, child) in named_submodules(module)? {
+        let descendants = module_tree(&child)?;
+        modules.push((name.clone(), child));
+        modules.extend(
+            descendants
+                .into_iter()
+                .map(|(path, module)| (format!("{name}::{path}"), module)),
+        );
+    }
+    Ok(modules)
+}
+
+/// Find the names and source files of modules declared in `module`.
+///
+/// Only `mod name;` declarations are considered; inline modules are skipped.
+///
+/// # Errors
+///
+/// Returns an error if the file could not be read or parsed, or if a declared
+/// module has no source file.
+pub fn named_submodules(
+    module: &ModuleFile,
+) -> Result<Vec<(String, ModuleFile)>, String> {
+    let path = &module.path;
+    let content =
+        crate::read_source(path).map_err(|error| error.to_string())?;
+    let file = syn::parse_file(&content)
+        .map_err(|error| format!("Failed to parse {path:?}: {error}"))?;
+    let dir = module
+        .submodule_dir()
+        .ok_or_else(|| format!("Could not get directory for {path:?}"))?;
+
+    file.items
+        .iter()
+        .filter_map(|item| match item {
+            syn::Item::Mod(item_mod) if item_mod.content.is_none() => {
+                Some(item_mod.ident.unraw().to_string())
+            }
+            _ => None,
+        })
+        .map(|name| Ok((name.clone(), find_module(&dir, &name, path)?)))
+        .collect()
+}
+
+/// Find the source file for module `name` in `dir`, declared in `parent`.
+///
+/// Checks for both `name.rs` and `name/mod.rs`. Like `rustc`, it’s an error if
+/// both exist, since it’s ambiguous which one is the module.
+///
+/// # Errors
+///
+/// Returns an error if neither or both of the files exist.
+fn find_module(
+    dir: &Path,
+    name: &str,
+    parent: &Path,
+) -> Result<ModuleFile, String> {
+    let file = dir.join(format!("{name}.rs"));
+    let mod_rs = dir.join(name).join("mod.rs");
+    match (file.is_file(), mod_rs.is_file()) {
+        (true, false) => Ok(ModuleFile { path: file, is_mod_rs: false }),
+        (false, true) => Ok(ModuleFile { path: mod_rs, is_mod_rs: true }),
+        (true, true) => Err(format!(
+            "Source file for module `{name}` declared in {parent:?} found at \
+            both {file:?} and {mod_rs:?}"
+        )),
+        (false, false) => Err(format!(
+            "Could not find source file for module `{name}` declared in \
+            {parent:?}"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture;
+    use assert2::assert;
+
+    /// Get file names relative to the test fixtures directory.
+    fn relative(modules: &[(String, ModuleFile)]) -> Vec<String> {
+        modules
+            .iter()
+            .map(|(_, file)| {
+                file.path
+                    .strip_prefix(fixture(""))
+                    .unwrap()
+                    .display()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn glob_sorted() {
+        assert!(
+            glob(&fixture("tree"), "**/*.rs", None)
+                .unwrap()
+                .iter()
+                .map(|path| path.strip_prefix(fixture("")).unwrap())
+                .collect::<Vec<_>>()
+                == [
+                    "tree/mod.rs",
+                    "tree/roots/mod.rs",
+                    "tree/roots/tip.rs",
+                    "tree/trunk/branch.rs",
+                    "tree/trunk.rs",
+                ]
+        );
+    }
+
+    #[test]
+    fn glob_exclude() {
+        assert!(
+            glob(&fixture("tree"), "*.rs", Some(&fixture("tree/mod.rs")))
+                .unwrap()
+                == [fixture("tree/trunk.rs")]
+        );
+    }
+
+    #[test]
+    fn glob_hidden() {
+        assert!(
+            glob(&fixture("tree"), "*.rs", None).unwrap()
+                == [fixture("tree/mod.rs"), fixture("tree/trunk.rs")]
+        );
+        assert!(let Err(_) = glob(&fixture("tree"), ".*.rs", None));
+    }
+
+    #[test]
+    fn outside_source_dirs() {
+        let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        assert!(!is_outside_source_dirs(crate_dir, &fixture("tree/mod.rs")));
+        assert!(!is_outside_source_dirs(
+            crate_dir,
+            &fixture("tree/../fruit/apple.rs")
+        ));
+        assert!(is_outside_source_dirs(
+            crate_dir,
+            &crate_dir.join("Cargo.toml")
+        ));
+        assert!(is_outside_source_dirs(crate_dir, &fixture("nonesuch.rs")));
+    }
+
+    #[test]
+    fn glob_no_matches() {
+        assert!(let Err(_) = glob(&fixture("tree"), "*.nonesuch", None));
+    }
+
+    #[test]
+    fn root_module_mod_rs() {
+        assert!(
+            root_module(&fixture("tree")).unwrap()
+                == ModuleFile { path: fixture("tree/mod.rs"), is_mod_rs: true }
+        );
+    }
+
+    #[test]
+    fn root_module_sibling() {
+        assert!(
+            root_module(&fixture("tree/trunk")).unwrap()
+                == ModuleFile {
+                    path: fixture("tree/trunk.rs"),
+                    is_mod_rs: false
+                }
+        );
+    }
+
+    #[test]
+    fn root_module_ambiguous() {
+        let dir = fixture("compile_fail/fixtures/ambiguous_module/twice");
+        assert!(let Err(_) = root_module(&dir));
+    }
+
+    #[test]
+    fn root_module_missing() {
+        assert!(let Err(_) = root_module(&fixture("nonesuch")));
+    }
+
+    #[test]
+    fn new_mod_rs() {
+        assert!(ModuleFile::new(fixture("tree/roots/mod.rs")).is_mod_rs);
+    }
+
+    #[test]
+    fn new_not_mod_rs() {
+        assert!(!ModuleFile::new(fixture("tree/trunk.rs")).is_mod_rs);
+    }
+
+    #[test]
+    fn submodules_in_order() {
+        let root = root_module(&fixture("tree")).unwrap();
+        assert!(
+            relative(&named_submodules(&root).unwrap())
+                == ["tree/roots/mod.rs", "tree/trunk.rs"]
+        );
+    }
+
+    #[test]
+    fn ambiguous_submodule() {
+        let root = ModuleFile::new(fixture(
+            "compile_fail/fixtures/ambiguous_module.rs",
+        ));
+        let error = named_submodules(&root).unwrap_err();
+        assert!(error.contains("found at both"));
+    }
+
+    #[test]
+    fn module_tree_paths() {
+        let root = root_module(&fixture("tree")).unwrap();
+        assert!(
+            module_tree(&root)
+                .unwrap()
+                .into_iter()
+                .map(|(path, _)| path)
+                .collect::<Vec<_>>()
+                == ["roots", "roots::tip", "trunk", "trunk::branch"]
+        );
+    }
+
+    #[test]
+    fn module_tree_in_order() {
+        let root = root_module(&fixture("tree")).unwrap();
+        assert!(
+            relative(&module_tree(&root).unwrap())
+                == [
+                    "tree/roots/mod.rs",
+                    "tree/roots/tip.rs",
+                    "tree/trunk.rs",
+                    "tree/trunk/branch.rs",
+                ]
+        );
+    }
+}
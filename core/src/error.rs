@@ -0,0 +1,180 @@
+//! Errors from reading docs out of files.
+
+use std::path::{Path, PathBuf};
+use std::{fmt, io};
+
+/// An error reading docs from a file.
+#[derive(Debug)]
+pub enum ExtractError {
+    /// The file couldn’t be read.
+    Io {
+        /// Path to the file.
+        path: PathBuf,
+
+        /// The underlying error.
+        error: io::Error,
+    },
+
+    /// The file couldn’t be parsed as Rust.
+    Parse {
+        /// Path to the file.
+        path: PathBuf,
+
+        /// The parser’s error message.
+        message: String,
+    },
+
+    /// The docs in the file were invalid, e.g. they had an unknown directive
+    /// or were missing a marker.
+    InvalidDocs {
+        /// Path to the file.
+        path: PathBuf,
+
+        /// What was wrong.
+        message: String,
+    },
+
+    /// The file had no module docs.
+    EmptyDocs {
+        /// Path to the file.
+        path: PathBuf,
+    },
+
+    /// The file’s docs didn’t have some of the required sections.
+    MissingSections {
+        /// Path to the file.
+        path: PathBuf,
+
+        /// The patterns that no heading matched.
+        headings: Vec<String>,
+    },
+
+    /// The file didn’t match any pattern in `owners`.
+    NoOwner {
+        /// Path to the file.
+        path: PathBuf,
+    },
+
+    /// The file didn’t contain the requested item.
+    ItemNotFound {
+        /// Path to the file.
+        path: PathBuf,
+
+        /// Name of the item.
+        name: String,
+    },
+
+    /// The file didn’t contain the requested inline module.
+    ModuleNotFound {
+        /// Path to the file.
+        path: PathBuf,
+
+        /// Path to the module within the file, e.g. `fruit::apple`.
+        name: String,
+    },
+}
+
+impl ExtractError {
+    /// Create an [`ExtractError::InvalidDocs`] from an error message.
+    #[must_use]
+    pub fn invalid_docs(path: &Path, message: String) -> Self {
+        Self::InvalidDocs { path: path.to_owned(), message }
+    }
+}
+
+impl fmt::Display for ExtractError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, error } => {
+                write!(
+                    f,
+                    "Failed to read {path:?}: {}",
+                    io_error_message(error)
+                )
+            }
+            Self::Parse { path, message } => {
+                write!(f, "Failed to parse {path:?}: {message}")
+            }
+            Self::InvalidDocs { path, message } => {
+                write!(f, "{message} in {path:?}")
+            }
+            Self::EmptyDocs { path } => write!(
+                f,
+                "No module docs found in {path:?}; module docs must be inner \
+                doc comments (`//!`)"
+            ),
+            Self::MissingSections { path, headings } => {
+                let headings: Vec<String> = headings
+                    .iter()
+                    .map(|heading| format!("{heading:?}"))
+                    .collect();
+                write!(
+                    f,
+                    "No section matching {} found in {path:?}",
+                    headings.join(" or ")
+                )
+            }
+            Self::NoOwner { path } => {
+                write!(f, "No owner found for {path:?} in `owners`")
+            }
+            Self::ItemNotFound { path, name } => {
+                write!(f, "No item named {name:?} found in {path:?}")
+            }
+            Self::ModuleNotFound { path, name } => {
+                write!(f, "No inline module {name:?} found in {path:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExtractError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Describe an I/O error.
+///
+/// Common errors get a fixed description, since the OS error message differs
+/// between platforms.
+fn io_error_message(error: &io::Error) -> String {
+    match error.kind() {
+        io::ErrorKind::NotFound => "file not found".to_owned(),
+        io::ErrorKind::PermissionDenied => "permission denied".to_owned(),
+        io::ErrorKind::IsADirectory => "is a directory".to_owned(),
+        io::ErrorKind::InvalidData => "file is not valid UTF-8".to_owned(),
+        _ => error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::assert;
+    use std::error::Error;
+
+    #[test]
+    fn io_message() {
+        let error = ExtractError::Io {
+            path: "a.rs".into(),
+            error: io::ErrorKind::NotFound.into(),
+        };
+        assert!(
+            error.to_string() == r#"Failed to read "a.rs": file not found"#
+        );
+        assert!(error.source().is_some());
+    }
+
+    #[test]
+    fn invalid_docs_message() {
+        let error = ExtractError::invalid_docs(
+            Path::new("a.rs"),
+            "Bad marker".to_owned(),
+        );
+        assert!(error.to_string() == r#"Bad marker in "a.rs""#);
+        assert!(error.source().is_none());
+    }
+}
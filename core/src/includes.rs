@@ -0,0 +1,169 @@
+//! Resolve `include_str!()` in `#![doc = ...]` attributes.
+//!
+//! Crates often keep their module docs in Markdown files and include them with
+//! `#![doc = include_str!("../docs/module.md")]`. Only the compiler can expand
+//! macros, so this evaluates the few that are commonly used for docs itself:
+//! `include_str!()`, `concat!()`, and `env!()`.
+
+use std::path::{Path, PathBuf};
+
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Expr, Lit, LitStr, Token};
+
+use crate::items::doc_value;
+use crate::{ExtractError, read_source};
+
+/// Get the docs from `attrs` in the file at `path`, along with the paths of
+/// the files they include.
+///
+/// This is like [`items::docs()`](crate::items::docs), but files included with
+/// `include_str!()` are read, relative to the directory containing `path`.
+/// Included files aren’t parsed, so any `include_str!()` in them is left
+/// alone. Attributes with other values are skipped.
+///
+/// # Errors
+///
+/// Returns an error if an included file couldn’t be read, or if its path
+/// couldn’t be evaluated.
+pub fn docs(
+    attrs: &[Attribute],
+    path: &Path,
+) -> Result<(String, Vec<PathBuf>), ExtractError> {
+    let mut lines = Vec::new();
+    let mut included = Vec::new();
+    for value in attrs.iter().filter_map(doc_value) {
+        if let Some(line) = eval(value, path, &mut included)? {
+            lines.push(line);
+        }
+    }
+    Ok((lines.join("\n"), included))
+}
+
+/// Evaluate a string expression in the file at `path`.
+///
+/// Returns `None` if the expression isn’t a string literal or a supported
+/// macro. The paths of included files are added to `included`.
+///
+/// # Errors
+///
+/// Returns an error if an included file couldn’t be read, or if a supported
+/// macro was invalid.
+fn eval(
+    expr: &Expr,
+    path: &Path,
+    included: &mut Vec<PathBuf>,
+) -> Result<Option<String>, ExtractError> {
+    let invalid =
+        |error: syn::Error| ExtractError::invalid_docs(path, error.to_string());
+    match expr {
+        Expr::Lit(expr) => match &expr.lit {
+            Lit::Str(lit) => Ok(Some(lit.value())),
+            _ => Ok(None),
+        },
+        Expr::Macro(expr) if expr.mac.path.is_ident("include_str") => {
+            let arg: Expr = expr.mac.parse_body().map_err(invalid)?;
+            let Some(file) = eval(&arg, path, included)? else {
+                return Err(ExtractError::invalid_docs(
+                    path,
+                    "Could not evaluate path passed to `include_str!()`"
+                        .to_owned(),
+                ));
+            };
+            let dir = path.parent().unwrap_or_else(|| Path::new(""));
+            let file = dir.join(file);
+            let source = read_source(&file)?;
+            included.push(file);
+            Ok(Some(source))
+        }
+        Expr::Macro(expr) if expr.mac.path.is_ident("concat") => {
+            let args = expr
+                .mac
+                .parse_body_with(
+                    Punctuated::<Expr, Token![,]>::parse_terminated,
+                )
+                .map_err(invalid)?;
+            let mut result = String::new();
+            for arg in &args {
+                let Some(value) = eval(arg, path, included)? else {
+                    return Ok(None);
+                };
+                result.push_str(&value);
+            }
+            Ok(Some(result))
+        }
+        Expr::Macro(expr) if expr.mac.path.is_ident("env") => {
+            let name: LitStr = expr.mac.parse_body().map_err(invalid)?;
+            std::env::var(name.value()).map(Some).map_err(|error| {
+                ExtractError::invalid_docs(
+                    path,
+                    format!("Could not read {:?}: {error}", name.value()),
+                )
+            })
+        }
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture;
+    use assert2::assert;
+
+    /// Get the docs from `source` as if it were at `tests/path`.
+    fn docs_at(source: &str, path: &str) -> Result<String, ExtractError> {
+        let file = syn::parse_file(source).unwrap();
+        docs(&file.attrs, &fixture(path)).map(|(docs, _)| docs)
+    }
+
+    #[test]
+    fn include_str() {
+        assert!(
+            docs_at(
+                "//! Before\n#![doc = include_str!(\"included.md\")]",
+                "markdown/including.rs",
+            )
+            .unwrap()
+                == " Before\n# Included\n\nFrom a Markdown file.\n"
+        );
+    }
+
+    #[test]
+    fn included_paths() {
+        let file =
+            syn::parse_file("#![doc = include_str!(\"included.md\")]").unwrap();
+        let (_, included) =
+            docs(&file.attrs, &fixture("markdown/including.rs")).unwrap();
+        assert!(included == [fixture("markdown/included.md")]);
+    }
+
+    #[test]
+    fn include_str_concat_env() {
+        assert!(
+            docs_at(
+                "#![doc = include_str!(concat!(\
+                    env!(\"CARGO_MANIFEST_DIR\"), \
+                    \"/../tests/markdown/included.md\"\
+                ))]",
+                "a.rs",
+            )
+            .unwrap()
+                == "# Included\n\nFrom a Markdown file.\n"
+        );
+    }
+
+    #[test]
+    fn include_str_missing() {
+        assert!(
+            let Err(ExtractError::Io { .. }) =
+                docs_at("#![doc = include_str!(\"nonesuch.md\")]", "a.rs")
+        );
+    }
+
+    #[test]
+    fn unsupported_macro() {
+        assert!(
+            docs_at("//! A\n#![doc = stringify!(B)]", "a.rs").unwrap() == " A"
+        );
+    }
+}
@@ -0,0 +1,974 @@
+//! Find items and their outer doc comments in Rust source.
+
+use proc_macro2::TokenTree;
+use syn::{
+    AttrStyle, Attribute, Ident, ImplItem, Item, ItemMacro, Meta, TraitItem,
+    Type, Visibility,
+};
+
+use crate::markdown;
+
+/// An item in a source file and its docs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ItemDocs {
+    /// How to refer to the item in an intra-doc link, e.g. `Apple`, `eat()`,
+    /// or `fruit!`.
+    pub link: String,
+
+    /// The item’s outer doc comments.
+    pub docs: String,
+
+    /// The item’s `#[deprecated]` attribute, if it has one.
+    pub deprecation: Option<Deprecation>,
+}
+
+impl ItemDocs {
+    /// Create an `ItemDocs` from an item’s link and its attributes.
+    fn new(link: String, attrs: &[Attribute]) -> Self {
+        Self { link, docs: docs(attrs), deprecation: deprecation(attrs) }
+    }
+
+    /// Get the first non-blank line of the item’s docs, trimmed.
+    pub fn first_line(&self) -> Option<&str> {
+        self.docs
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+    }
+}
+
+/// Find the public items in `items`, e.g. the top level of a file.
+///
+/// Items are returned in source order. `pub(crate)` and other restricted
+/// items are skipped, as are `use` declarations and `impl` blocks. Macros are
+/// included if they are `#[macro_export]`, even if they’re defined in an inline
+/// module, since exported macros are always available at the crate root.
+#[must_use]
+pub fn public_items(items: &[Item]) -> Vec<ItemDocs> {
+    items
+        .iter()
+        .flat_map(|item| {
+            let nested = match item {
+                Item::Mod(item_mod) => item_mod
+                    .content
+                    .as_ref()
+                    .map(|(_, items)| nested_macros(items))
+                    .unwrap_or_default(),
+                _ => Vec::new(),
+            };
+            public_item(item).into_iter().chain(
+                nested
+                    .into_iter()
+                    .filter(|item| is_macro_export(&item.attrs))
+                    .filter_map(macro_docs),
+            )
+        })
+        .collect()
+}
+
+/// Get the link and docs of `item` if it’s public.
+fn public_item(item: &Item) -> Option<ItemDocs> {
+    let (ident, attrs, vis) = named_item(item)?;
+    let exported = match vis {
+        Some(vis) => is_pub(vis),
+        None => is_macro_export(attrs),
+    };
+    if !exported {
+        return None;
+    }
+    let link = match item {
+        Item::Fn(_) => format!("{ident}()"),
+        Item::Macro(_) => format!("{ident}!"),
+        _ => ident.to_string(),
+    };
+    Some(ItemDocs::new(link, attrs))
+}
+
+/// Get the link and docs of a `macro_rules!` macro.
+fn macro_docs(item: &ItemMacro) -> Option<ItemDocs> {
+    Some(ItemDocs::new(
+        format!("{}!", item.ident.as_ref()?),
+        &item.attrs,
+    ))
+}
+
+/// Get the outer docs of the item named `name` at the top level of `file`.
+///
+/// If `name` ends with `!`, e.g. `fruit!`, only `macro_rules!` macros match.
+/// Macros are also found in inline modules, since `#[macro_export]` makes them
+/// available at the crate root no matter where they’re defined.
+///
+/// If `name` starts with `impl`, e.g. `impl Encoder for JsonCodec` or
+/// `impl JsonCodec`, the docs of the first matching `impl` block are returned.
+///
+/// Returns `None` if there is no such item.
+#[must_use]
+pub fn item_docs(file: &syn::File, name: &str) -> Option<String> {
+    if let Some(target) = name.strip_prefix("impl ") {
+        return impl_docs(file, target);
+    }
+    if let Some(name) = name.strip_suffix('!') {
+        return nested_macros(&file.items)
+            .into_iter()
+            .find(|item| item.ident.as_ref().is_some_and(|ident| ident == name))
+            .map(|item| docs(&item.attrs));
+    }
+
+    file.items
+        .iter()
+        .find_map(|item| {
+            let (ident, attrs, _) = named_item(item)?;
+            (ident == name).then(|| docs(attrs))
+        })
+        .or_else(|| item_docs(file, &format!("{name}!")))
+}
+
+/// Get the outer docs of an `impl` block at the top level of `file`.
+///
+/// `target` is `Trait for Type` for a trait `impl`, or just `Type` for an
+/// inherent `impl`. Paths and generics are ignored, so `Encoder for JsonCodec`
+/// matches `impl<T> codec::Encoder<T> for JsonCodec<T>`.
+fn impl_docs(file: &syn::File, target: &str) -> Option<String> {
+    let (trait_name, type_) = match target.split_once(" for ") {
+        Some((trait_name, type_)) => (Some(trait_name.trim()), type_.trim()),
+        None => (None, target.trim()),
+    };
+    file.items.iter().find_map(|item| match item {
+        Item::Impl(item_impl)
+            if type_name(&item_impl.self_ty)
+                .is_some_and(|ident| ident == type_)
+                && item_impl
+                    .trait_
+                    .as_ref()
+                    .and_then(|(_, path, _)| path.segments.last())
+                    .map(|segment| segment.ident.to_string())
+                    .as_deref()
+                    == trait_name =>
+        {
+            Some(docs(&item_impl.attrs))
+        }
+        _ => None,
+    })
+}
+
+/// Get the code in the body of the function named `name` in `source`.
+///
+/// The first function with that name is used, even if it’s in an inline
+/// module or an `impl` block. The code is dedented, and blank lines around it
+/// are removed. Comments are kept, since they’re often part of an example.
+///
+/// Returns `None` if there is no such function.
+#[must_use]
+pub fn function_code(source: &str, name: &str) -> Option<String> {
+    let body = markdown::dedent(crate::scanner::function_body(source, name)?);
+    let lines: Vec<&str> = body.lines().collect();
+    let start = lines.iter().position(|line| !line.trim().is_empty());
+    let end = lines.iter().rposition(|line| !line.trim().is_empty());
+    Some(match (start, end) {
+        (Some(start), Some(end)) => lines[start..=end].join("\n"),
+        _ => String::new(),
+    })
+}
+
+/// Get the outer docs of the first function named `name` in `items`.
+///
+/// Functions in inline modules are also found, so that test functions in a
+/// `mod tests` can be used, as are methods in `impl` blocks and traits.
+/// Functions without a body are skipped, so this finds the same function as
+/// [`function_code()`]. Returns `None` if there is no such function.
+#[must_use]
+pub fn function_docs(items: &[Item], name: &str) -> Option<String> {
+    items.iter().find_map(|item| match item {
+        Item::Fn(item_fn) if item_fn.sig.ident == name => {
+            Some(docs(&item_fn.attrs))
+        }
+        Item::Impl(item_impl) => {
+            item_impl.items.iter().find_map(|item| match item {
+                ImplItem::Fn(item_fn) if item_fn.sig.ident == name => {
+                    Some(docs(&item_fn.attrs))
+                }
+                _ => None,
+            })
+        }
+        Item::Trait(item_trait) => {
+            item_trait.items.iter().find_map(|item| match item {
+                TraitItem::Fn(item_fn)
+                    if item_fn.sig.ident == name
+                        && item_fn.default.is_some() =>
+                {
+                    Some(docs(&item_fn.attrs))
+                }
+                _ => None,
+            })
+        }
+        Item::Mod(item_mod) => {
+            function_docs(&item_mod.content.as_ref()?.1, name)
+        }
+        _ => None,
+    })
+}
+
+/// Find the inner attributes of an inline module in `file`.
+///
+/// `path` is relative to the file, e.g. `fruit::apple` for
+/// `mod fruit { mod apple { ... } }`. The attributes include inner doc
+/// comments, but not the module’s outer doc comments.
+///
+/// Returns `None` if there is no such inline module. Modules declared with
+/// `mod name;` are in other files, so they don’t match.
+#[must_use]
+pub fn inline_module_attrs(
+    file: &syn::File,
+    path: &str,
+) -> Option<Vec<Attribute>> {
+    let (attrs, _) = inline_module(file, path)?;
+    Some(
+        attrs
+            .iter()
+            .filter(|attr| matches!(attr.style, AttrStyle::Inner(_)))
+            .cloned()
+            .collect(),
+    )
+}
+
+/// Find the items of an inline module in `file`.
+///
+/// `path` is as in [`inline_module_attrs()`]. Returns `None` if there is no
+/// such inline module.
+#[must_use]
+pub fn inline_module_items<'a>(
+    file: &'a syn::File,
+    path: &str,
+) -> Option<&'a [Item]> {
+    inline_module(file, path).map(|(_, items)| items)
+}
+
+/// Find the attributes and items of an inline module in `file`.
+fn inline_module<'a>(
+    file: &'a syn::File,
+    path: &str,
+) -> Option<(&'a [Attribute], &'a [Item])> {
+    let mut items = &file.items;
+    let mut attrs = &file.attrs;
+    for name in path.split("::") {
+        let item_mod = items.iter().find_map(|item| match item {
+            Item::Mod(item_mod) if item_mod.ident == name => Some(item_mod),
+            _ => None,
+        })?;
+        items = &item_mod.content.as_ref()?.1;
+        attrs = &item_mod.attrs;
+    }
+    Some((attrs, items))
+}
+
+/// Find the `macro_rules!` macros in `items` and inline modules, recursively.
+///
+/// Macros are returned in source order.
+fn nested_macros(items: &[Item]) -> Vec<&ItemMacro> {
+    items
+        .iter()
+        .flat_map(|item| match item {
+            Item::Macro(item_macro) if item_macro.ident.is_some() => {
+                vec![item_macro]
+            }
+            Item::Mod(item_mod) => item_mod
+                .content
+                .as_ref()
+                .map(|(_, items)| nested_macros(items))
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        })
+        .collect()
+}
+
+/// Do `attrs` include `#[macro_export]`?
+fn is_macro_export(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .any(|attr| attr.path().is_ident("macro_export"))
+}
+
+/// Get the name, attributes, and visibility of an item.
+///
+/// Macros have no visibility. Returns `None` for items without a name, such as
+/// `impl` blocks and `use` declarations.
+fn named_item(
+    item: &Item,
+) -> Option<(&Ident, &[Attribute], Option<&Visibility>)> {
+    match item {
+        Item::Const(item) => Some((&item.ident, &item.attrs, Some(&item.vis))),
+        Item::Enum(item) => Some((&item.ident, &item.attrs, Some(&item.vis))),
+        Item::Fn(item) => Some((&item.sig.ident, &item.attrs, Some(&item.vis))),
+        Item::Macro(item) => Some((item.ident.as_ref()?, &item.attrs, None)),
+        Item::Mod(item) => Some((&item.ident, &item.attrs, Some(&item.vis))),
+        Item::Static(item) => Some((&item.ident, &item.attrs, Some(&item.vis))),
+        Item::Struct(item) => Some((&item.ident, &item.attrs, Some(&item.vis))),
+        Item::Trait(item) => Some((&item.ident, &item.attrs, Some(&item.vis))),
+        Item::TraitAlias(item) => {
+            Some((&item.ident, &item.attrs, Some(&item.vis)))
+        }
+        Item::Type(item) => Some((&item.ident, &item.attrs, Some(&item.vis))),
+        Item::Union(item) => Some((&item.ident, &item.attrs, Some(&item.vis))),
+        _ => None,
+    }
+}
+
+/// Find the methods of the trait or inherent `impl` named `name` in `file`.
+///
+/// If there is a trait named `name`, all of its methods are returned.
+/// Otherwise, the public methods from `impl name` blocks are returned. Methods
+/// are in source order.
+///
+/// Returns `None` if there is no trait or inherent `impl` named `name`.
+#[must_use]
+pub fn methods(file: &syn::File, name: &str) -> Option<Vec<ItemDocs>> {
+    let trait_methods = file.items.iter().find_map(|item| match item {
+        Item::Trait(item_trait) if item_trait.ident == name => Some(
+            item_trait
+                .items
+                .iter()
+                .filter_map(|item| match item {
+                    TraitItem::Fn(item) => Some(ItemDocs::new(
+                        format!("{}()", item.sig.ident),
+                        &item.attrs,
+                    )),
+                    _ => None,
+                })
+                .collect(),
+        ),
+        _ => None,
+    });
+    if trait_methods.is_some() {
+        return trait_methods;
+    }
+
+    let mut impls = file
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Impl(item_impl)
+                if item_impl.trait_.is_none()
+                    && type_name(&item_impl.self_ty)
+                        .is_some_and(|ident| ident == name) =>
+            {
+                Some(item_impl)
+            }
+            _ => None,
+        })
+        .peekable();
+    impls.peek()?;
+    Some(
+        impls
+            .flat_map(|item_impl| &item_impl.items)
+            .filter_map(|item| match item {
+                ImplItem::Fn(item) if is_pub(&item.vis) => Some(ItemDocs::new(
+                    format!("{}()", item.sig.ident),
+                    &item.attrs,
+                )),
+                _ => None,
+            })
+            .collect(),
+    )
+}
+
+/// Get the name of a type, ignoring any path and generics.
+fn type_name(ty: &Type) -> Option<&syn::Ident> {
+    match ty {
+        Type::Path(type_path) => {
+            type_path.path.segments.last().map(|segment| &segment.ident)
+        }
+        _ => None,
+    }
+}
+
+/// Is the visibility plain `pub`?
+const fn is_pub(vis: &Visibility) -> bool {
+    matches!(vis, Visibility::Public(_))
+}
+
+/// Get the docs from a list of attributes.
+///
+/// Each `#[doc = "..."]` attribute (including doc comments) becomes a line.
+/// Other attributes are skipped, including `doc(...)` lists like `doc(hidden)`;
+/// see [`is_hidden()`].
+#[must_use]
+pub fn docs(attrs: &[Attribute]) -> String {
+    attrs
+        .iter()
+        .filter_map(|attr| {
+            if let Some(syn::Expr::Lit(expr_lit)) = doc_value(attr)
+                && let syn::Lit::Str(lit_str) = &expr_lit.lit
+            {
+                Some(lit_str.value())
+            } else {
+                // Skip attributes other than a doc attributes with a value.
+                None
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Get the value of a `#[doc = ...]` attribute.
+pub(crate) fn doc_value(attr: &Attribute) -> Option<&syn::Expr> {
+    match &attr.meta {
+        Meta::NameValue(meta) if meta.path.is_ident("doc") => Some(&meta.value),
+        _ => None,
+    }
+}
+
+/// Do the attributes include `doc(hidden)`?
+#[must_use]
+pub fn is_hidden(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        let Meta::List(list) = &attr.meta else {
+            return false;
+        };
+        list.path.is_ident("doc")
+            && list.tokens.clone().into_iter().any(|token| {
+                matches!(&token, TokenTree::Ident(ident) if ident == "hidden")
+            })
+    })
+}
+
+/// An item’s `#[deprecated]` attribute.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Deprecation {
+    /// The version the item was deprecated in, if given.
+    pub since: Option<String>,
+
+    /// Why the item is deprecated or what to use instead, if given.
+    pub note: Option<String>,
+}
+
+impl Deprecation {
+    /// Is the item deprecated in `version` of its crate?
+    ///
+    /// This is true unless `since` is a later version than `version`, since
+    /// Rust allows deprecating items in a future version. Versions that
+    /// aren’t like `1.2.3` are assumed to have already been released.
+    #[must_use]
+    pub fn applies_to(&self, version: &str) -> bool {
+        /// Parse the numbers of a version like `1.2.3`, ignoring pre-releases.
+        fn numbers(version: &str) -> Option<Vec<u64>> {
+            let version = version.split(['-', '+']).next().unwrap_or_default();
+            version
+                .split('.')
+                .map(|number| number.parse().ok())
+                .collect()
+        }
+        match self.since.as_deref().and_then(numbers) {
+            Some(since) => {
+                numbers(version).is_none_or(|version| since <= version)
+            }
+            None => true,
+        }
+    }
+}
+
+/// Get the `#[deprecated]` attribute from `attrs`, if there is one.
+fn deprecation(attrs: &[Attribute]) -> Option<Deprecation> {
+    let attr = attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("deprecated"))?;
+    let mut deprecation = Deprecation { since: None, note: None };
+    match &attr.meta {
+        Meta::NameValue(meta) => {
+            if let syn::Expr::Lit(expr_lit) = &meta.value
+                && let syn::Lit::Str(lit_str) = &expr_lit.lit
+            {
+                deprecation.note = Some(lit_str.value());
+            }
+        }
+        Meta::List(_) => {
+            // Unknown keys are the compiler’s problem, so ignore errors.
+            let _ = attr.parse_nested_meta(|meta| {
+                let value = meta.value()?.parse::<syn::LitStr>()?.value();
+                if meta.path.is_ident("since") {
+                    deprecation.since = Some(value);
+                } else if meta.path.is_ident("note") {
+                    deprecation.note = Some(value);
+                }
+                Ok(())
+            });
+        }
+        Meta::Path(_) => {}
+    }
+    Some(deprecation)
+}
+
+/// Split `items` into those that aren’t deprecated in `version` and those
+/// that are.
+#[must_use]
+pub fn partition_deprecated(
+    items: Vec<ItemDocs>,
+    version: &str,
+) -> (Vec<ItemDocs>, Vec<ItemDocs>) {
+    items.into_iter().partition(|item| {
+        !item
+            .deprecation
+            .as_ref()
+            .is_some_and(|deprecation| deprecation.applies_to(version))
+    })
+}
+
+/// Generate a “Deprecated” section listing `items` with their deprecation
+/// notes.
+///
+/// Items without a note get the first line of their docs instead.
+#[must_use]
+pub fn deprecated_section(items: &[ItemDocs]) -> String {
+    let list = items
+        .iter()
+        .map(|item| {
+            let deprecation = item.deprecation.as_ref();
+            let since = deprecation
+                .and_then(|deprecation| deprecation.since.as_deref())
+                .map(|since| format!(" (since {since})"))
+                .unwrap_or_default();
+            match deprecation
+                .and_then(|deprecation| deprecation.note.as_deref())
+                .or_else(|| item.first_line())
+            {
+                Some(note) => format!("* [`{}`]{since}: {note}", item.link),
+                None => format!("* [`{}`]{since}", item.link),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!("# Deprecated\n\n{list}")
+}
+
+/// Generate a Markdown list of items with the first line of their docs.
+#[must_use]
+pub fn summary(items: &[ItemDocs]) -> String {
+    items
+        .iter()
+        .map(|item| match item.first_line() {
+            Some(line) => format!("* [`{}`]: {line}", item.link),
+            None => format!("* [`{}`]", item.link),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Generate an “Available methods” section with the full docs of `methods`.
+#[must_use]
+pub fn methods_section(methods: &[ItemDocs]) -> String {
+    let mut sections = vec!["# Available methods".to_owned()];
+    sections.extend(methods.iter().map(|method| {
+        if method.docs.trim().is_empty() {
+            format!("## `{}`", method.link)
+        } else {
+            format!("## `{}`\n\n{}", method.link, method.docs)
+        }
+    }));
+    sections.join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::assert;
+
+    /// Get the public items from `source`.
+    fn items(source: &str) -> Vec<ItemDocs> {
+        public_items(&syn::parse_file(source).unwrap().items)
+    }
+
+    #[test]
+    fn only_public_items() {
+        assert!(
+            items(
+                r"
+/// A struct.
+pub struct A;
+/// Private.
+struct B;
+/// Crate visible.
+pub(crate) struct C;
+pub use std::fmt;
+impl A {}
+"
+            ) == [ItemDocs {
+                link: "A".to_owned(),
+                docs: " A struct.".to_owned(),
+                deprecation: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn item_kinds() {
+        assert!(
+            items(
+                r"
+pub const A: u8 = 0;
+pub enum B {}
+pub fn c() {}
+#[macro_export]
+macro_rules! d { () => {} }
+macro_rules! private { () => {} }
+pub mod e {}
+pub static F: u8 = 0;
+pub struct G;
+pub trait H {}
+pub type I = u8;
+pub union J { a: u8 }
+"
+            )
+            .into_iter()
+            .map(|item| item.link)
+            .collect::<Vec<_>>()
+                == ["A", "B", "c()", "d!", "e", "F", "G", "H", "I", "J"]
+        );
+    }
+
+    #[test]
+    fn item_docs_by_name() {
+        let file = syn::parse_file(
+            r"
+/// Public.
+pub struct A;
+/// Private.
+fn b() {}
+impl A {}
+",
+        )
+        .unwrap();
+        assert!(item_docs(&file, "b") == Some(" Private.".to_owned()));
+        assert!(item_docs(&file, "A") == Some(" Public.".to_owned()));
+        assert!(item_docs(&file, "C") == None);
+    }
+
+    #[test]
+    fn macro_docs_by_name() {
+        let file = syn::parse_file(
+            r"
+/// Struct.
+pub struct a;
+mod inner {
+    mod deeper {
+        /// Nested macro.
+        #[macro_export]
+        macro_rules! a { () => {} }
+    }
+}
+/// Top level macro.
+macro_rules! b { () => {} }
+",
+        )
+        .unwrap();
+        assert!(item_docs(&file, "a") == Some(" Struct.".to_owned()));
+        assert!(item_docs(&file, "a!") == Some(" Nested macro.".to_owned()));
+        assert!(item_docs(&file, "b") == Some(" Top level macro.".to_owned()));
+        assert!(item_docs(&file, "b!") == Some(" Top level macro.".to_owned()));
+        assert!(item_docs(&file, "c!") == None);
+    }
+
+    #[test]
+    fn impl_docs_by_name() {
+        let file = syn::parse_file(
+            r"
+/// Inherent.
+impl Codec {}
+impl Decoder for Codec {}
+/// Encoder.
+impl<T> codec::Encoder<T> for Codec<T> {}
+",
+        )
+        .unwrap();
+        assert!(
+            item_docs(&file, "impl Codec") == Some(" Inherent.".to_owned())
+        );
+        assert!(
+            item_docs(&file, "impl Encoder for Codec")
+                == Some(" Encoder.".to_owned())
+        );
+        assert!(
+            item_docs(&file, "impl Decoder for Codec") == Some(String::new())
+        );
+        assert!(item_docs(&file, "impl Encoder for Other") == None);
+    }
+
+    #[test]
+    fn inline_module() {
+        let file = syn::parse_file(
+            r"
+//! File.
+/// Outer.
+mod fruit {
+    //! Fruit.
+    mod apple {
+        //! Apple.
+        #![doc(hidden)]
+    }
+    mod orange;
+}
+",
+        )
+        .unwrap();
+        let module_docs =
+            |path| inline_module_attrs(&file, path).map(|attrs| docs(&attrs));
+        assert!(module_docs("fruit") == Some(" Fruit.".to_owned()));
+        assert!(module_docs("fruit::apple") == Some(" Apple.".to_owned()));
+        assert!(module_docs("fruit::orange") == None);
+        assert!(module_docs("apple") == None);
+        assert!(is_hidden(
+            &inline_module_attrs(&file, "fruit::apple").unwrap()
+        ));
+    }
+
+    #[test]
+    fn nested_exported_macros() {
+        assert!(
+            items(
+                r"
+pub mod a {
+    #[macro_export]
+    macro_rules! b { () => {} }
+    macro_rules! private { () => {} }
+}
+mod c {
+    mod d {
+        #[macro_export]
+        macro_rules! e { () => {} }
+    }
+}
+"
+            )
+            .into_iter()
+            .map(|item| item.link)
+            .collect::<Vec<_>>()
+                == ["a", "b!", "e!"]
+        );
+    }
+
+    /// Get the methods of `name` in `source`.
+    fn method_links(source: &str, name: &str) -> Option<Vec<String>> {
+        methods(&syn::parse_file(source).unwrap(), name).map(|methods| {
+            methods.into_iter().map(|method| method.link).collect()
+        })
+    }
+
+    #[test]
+    fn trait_methods() {
+        assert!(
+            method_links(
+                r"
+impl Ext {
+    pub fn inherent() {}
+}
+pub trait Ext {
+    const A: u8;
+    fn a(&self);
+    fn b(&self) {}
+}
+",
+                "Ext"
+            ) == Some(vec!["a()".to_owned(), "b()".to_owned()])
+        );
+    }
+
+    #[test]
+    fn inherent_methods() {
+        assert!(
+            method_links(
+                r"
+impl Apple {
+    pub fn a(&self) {}
+    fn private(&self) {}
+}
+impl Default for Apple {
+    fn default() -> Self { Self }
+}
+impl<T> crate::Apple<T> {
+    pub fn b(&self) {}
+}
+",
+                "Apple"
+            ) == Some(vec!["a()".to_owned(), "b()".to_owned()])
+        );
+    }
+
+    #[test]
+    fn no_methods() {
+        assert!(method_links("struct Apple;", "Apple") == None);
+        assert!(method_links("impl Apple {}", "Apple") == Some(vec![]));
+    }
+
+    #[test]
+    fn section() {
+        assert!(
+            methods_section(&[
+                ItemDocs {
+                    link: "a()".to_owned(),
+                    docs: " Does a.".to_owned(),
+                    deprecation: None,
+                },
+                ItemDocs {
+                    link: "b()".to_owned(),
+                    docs: String::new(),
+                    deprecation: None,
+                },
+            ]) == "# Available methods\n\n## `a()`\n\n Does a.\n\n## `b()`"
+        );
+    }
+
+    #[test]
+    fn summary_first_lines() {
+        assert!(
+            summary(&items(
+                r"
+///
+/// First line.
+/// Second line.
+pub struct A;
+pub fn b() {}
+"
+            )) == "* [`A`]: First line.\n* [`b()`]"
+        );
+    }
+
+    #[test]
+    fn deprecations() {
+        let items = items(
+            r#"
+#[deprecated]
+pub fn a() {}
+#[deprecated = "Use `a()`."]
+pub fn b() {}
+#[deprecated(since = "0.2.0", note = "Use `b()`.")]
+pub fn c() {}
+pub fn d() {}
+"#,
+        );
+        let deprecations: Vec<_> =
+            items.iter().map(|item| item.deprecation.clone()).collect();
+        assert!(
+            deprecations
+                == [
+                    Some(Deprecation { since: None, note: None }),
+                    Some(Deprecation {
+                        since: None,
+                        note: Some("Use `a()`.".to_owned()),
+                    }),
+                    Some(Deprecation {
+                        since: Some("0.2.0".to_owned()),
+                        note: Some("Use `b()`.".to_owned()),
+                    }),
+                    None,
+                ]
+        );
+    }
+
+    #[test]
+    fn deprecation_versions() {
+        let since = |since: &str| Deprecation {
+            since: Some(since.to_owned()),
+            note: None,
+        };
+        assert!(since("0.2.0").applies_to("0.2.0"));
+        assert!(since("0.2.0").applies_to("0.10.0"));
+        assert!(!since("0.2.0").applies_to("0.1.9"));
+        assert!(since("0.2.0").applies_to("0.2.0-rc.1"));
+        assert!(since("soon").applies_to("0.1.0"));
+    }
+
+    #[test]
+    fn deprecated_appendix() {
+        let (current, deprecated) = partition_deprecated(
+            items(
+                r#"
+/// Fine.
+pub fn a() {}
+/// Old.
+#[deprecated]
+pub fn b() {}
+#[deprecated(since = "0.2.0", note = "Use `a()`.")]
+pub fn c() {}
+#[deprecated(since = "1.0.0")]
+pub fn d() {}
+"#,
+            ),
+            "0.2.0",
+        );
+        assert!(summary(&current) == "* [`a()`]: Fine.\n* [`d()`]");
+        assert!(
+            deprecated_section(&deprecated)
+                == "# Deprecated\n\n* [`b()`]: Old.\n\
+                * [`c()`] (since 0.2.0): Use `a()`."
+        );
+    }
+
+    #[test]
+    fn functions() {
+        let source = r"
+mod tests {
+    /// Add numbers.
+    #[test]
+    fn add() {
+
+        // Numbers.
+        assert_eq!(1 + 1, 2);
+        if true {
+            println!();
+        }
+
+    }
+}
+";
+        assert!(
+            function_code(source, "add").as_deref()
+                == Some(
+                    "// Numbers.\nassert_eq!(1 + 1, 2);\nif true {\n    \
+                    println!();\n}"
+                )
+        );
+        let file = syn::parse_file(source).unwrap();
+        assert!(
+            function_docs(&file.items, "add")
+                == Some(" Add numbers.".to_owned())
+        );
+        assert!(function_docs(&file.items, "tests") == None);
+    }
+
+    #[test]
+    fn function_methods() {
+        let source = r"
+trait Fill {
+    /// Declared.
+    fn fill(&mut self);
+
+    /// Default.
+    fn pour(&self) {
+        drop(self);
+    }
+}
+
+impl Basket {
+    /// Method.
+    fn fill(&mut self) {
+        self.full = true;
+    }
+}
+
+/// Free function.
+fn fill() {}
+";
+        let file = syn::parse_file(source).unwrap();
+        assert!(
+            function_code(source, "fill").as_deref()
+                == Some("self.full = true;")
+        );
+        assert!(
+            function_docs(&file.items, "fill") == Some(" Method.".to_owned())
+        );
+        assert!(
+            function_code(source, "pour").as_deref() == Some("drop(self);")
+        );
+        assert!(
+            function_docs(&file.items, "pour") == Some(" Default.".to_owned())
+        );
+    }
+}
@@ -0,0 +1,1160 @@
+//! # Read module documentation from Rust source files.
+//!
+//! This is the library behind the [`read-doc`] macros. It extracts and
+//! processes module docs exactly as the macros do, so that tools like
+//! documentation site generators can use the same logic at runtime.
+//!
+//! ```no_run
+//! use read_doc_core::DocExtractor;
+//!
+//! let extractor = DocExtractor { shift_headings: 1, ..Default::default() };
+//! if let Some(docs) = extractor.extract_file("src/fruit.rs".as_ref())? {
+//!     println!("{docs}");
+//! }
+//! # Ok::<(), read_doc_core::ExtractError>(())
+//! ```
+//!
+//! The modules contain the building blocks used by the macros, such as
+//! finding module files and reading the docs of individual items.
+//!
+//! # Build scripts
+//!
+//! Where the macros can’t be used, a build script can write docs to a file
+//! instead, to be included with `#![doc = include_str!(...)]`:
+//!
+//! ```no_run
+//! // build.rs
+//! use read_doc_core::DocExtractor;
+//! use std::path::{Path, PathBuf};
+//! use std::{env, fs};
+//!
+//! let paths = [PathBuf::from("src/apple.rs"), PathBuf::from("src/orange.rs")];
+//! for path in &paths {
+//!     println!("cargo::rerun-if-changed={}", path.display());
+//! }
+//! let docs = DocExtractor::default()
+//!     .extract_combined(&paths)
+//!     .unwrap_or_else(|errors| panic!("Couldn’t read docs: {errors:?}"));
+//! let out_dir = env::var_os("OUT_DIR").unwrap();
+//! fs::write(Path::new(&out_dir).join("fruit.md"), docs)?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+//! Then in `src/lib.rs`:
+//!
+//! ```ignore
+//! #![doc = include_str!(concat!(env!("OUT_DIR"), "/fruit.md"))]
+//! ```
+//!
+//! # Minimum supported Rust version
+//!
+//! Currently the minimum supported Rust version (MSRV) is **1.88**. Future
+//! increases in the MSRV will require a major version bump.
+//!
+//! [`read-doc`]: https://docs.rs/read-doc/latest/read_doc/
+
+// Lint configuration in Cargo.toml isn't supported by cargo-geiger.
+#![forbid(unsafe_code)]
+
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use std::{fs, thread};
+
+pub mod assets;
+mod cache;
+pub mod directives;
+pub mod discover;
+mod error;
+mod includes;
+pub mod items;
+pub mod map;
+pub mod markdown;
+pub mod owners;
+mod scanner;
+pub mod template;
+pub mod text;
+pub mod toc;
+
+use directives::Directives;
+pub use error::ExtractError;
+
+/// Extracts and processes module docs from Rust source files.
+///
+/// Each field corresponds to an option of the `read-doc` macros. The default
+/// reads every inner doc comment at the top of a file and leaves it as is.
+#[derive(Clone, Debug, Default)]
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "each bool is an independent option"
+)]
+pub struct DocExtractor {
+    /// Read inner docs from anywhere in each file, not just the top.
+    pub anywhere: bool,
+
+    /// Only keep audience regions for this audience. `None` keeps every
+    /// region.
+    pub audience: Option<String>,
+
+    /// Only include the lines between these start and end marker lines.
+    pub between: Option<(String, String)>,
+
+    /// Which blocks of inner doc comments to read from each file.
+    pub blocks: Blocks,
+
+    /// Collapse runs of blank lines outside of code blocks into one, and
+    /// remove blank lines around each file’s docs.
+    pub collapse_blank_lines: bool,
+
+    /// How to treat Rust code blocks that would be run as doctests.
+    pub doctests: Doctests,
+
+    /// How to escape special characters in the output.
+    pub escape: Escape,
+
+    /// Headings of sections to remove.
+    pub exclude_sections: Vec<String>,
+
+    /// The format to produce.
+    pub format: Format,
+
+    /// Text to add to the end of each heading, e.g. to make headings that
+    /// appear in several files unique. Placeholders are replaced as in
+    /// `prefix`.
+    pub heading_suffix: String,
+
+    /// What to do with files marked `#![doc(hidden)]`.
+    pub hidden: Hidden,
+
+    /// If a file can’t be parsed, read the inner docs at its start anyway.
+    pub lenient: bool,
+
+    /// Only include these lines of each file’s docs, counting from 1.
+    pub lines: Option<Range<usize>>,
+
+    /// What to do if a file has no docs.
+    pub missing: Missing,
+
+    /// Read the inner docs of this inline module, e.g. `fruit::apple` for
+    /// `mod fruit { mod apple { ... } }`, instead of the file’s docs.
+    pub module: Option<String>,
+
+    /// Turn inline code naming a public item of each file, e.g. `` `Apple` ``,
+    /// into an intra-doc link.
+    pub link_items: bool,
+
+    /// Who owns each file. If set, every file must have an owner.
+    pub owners: Option<owners::Owners>,
+
+    /// Languages of code blocks to turn into `<pre>` HTML blocks, e.g. for
+    /// diagrams.
+    pub pre_blocks: Vec<String>,
+
+    /// Text to add before each file’s docs. `{file}` is replaced with the
+    /// file’s name, `{module}` with the name of its module, and `{owner}`
+    /// with its owner.
+    pub prefix: String,
+
+    /// Return the inner docs at the start of each file exactly as written,
+    /// including `//!` markers, without any other processing.
+    pub raw: bool,
+
+    /// Patterns for headings that each file’s docs must have, e.g.
+    /// `Examples`. May contain `*` and `?` wildcards.
+    pub require_sections: Vec<String>,
+
+    /// Where to serve files that docs refer to with relative URLs, e.g.
+    /// `![](diagram.png)`, so that the URLs work wherever the docs are
+    /// included.
+    pub rewrite_assets: Option<assets::AssetUrls>,
+
+    /// Path prefixes to replace within Rust code blocks, e.g. to change
+    /// `crate::child::Foo` to `crate::Foo`.
+    pub rewrite_doctests: Vec<(String, String)>,
+
+    /// Headings of sections to keep, or to remove if prefixed with `!`. May
+    /// contain `*` and `?` wildcards.
+    pub sections: Vec<String>,
+
+    /// How many levels to shift headings by.
+    pub shift_headings: usize,
+
+    /// Extra patterns that mark a license header, if license headers should
+    /// be stripped.
+    pub strip_license: Option<Vec<String>>,
+
+    /// Only read docs written in these styles. Empty means all styles.
+    pub styles: Vec<DocStyle>,
+
+    /// Reduce each file’s docs to a list item with its first paragraph.
+    pub summary: bool,
+
+    /// Text to add after each file’s docs. Placeholders are replaced as in
+    /// `prefix`.
+    pub suffix: String,
+
+    /// Remove the leading whitespace common to every line.
+    pub trim: bool,
+}
+
+impl DocExtractor {
+    /// Read and process the docs from a single file.
+    ///
+    /// Returns `None` if the file has no docs, has a `skip` directive, or is
+    /// hidden and `hidden` is [`Hidden::Skip`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file couldn’t be read or parsed, or if it had
+    /// an invalid directive.
+    pub fn extract_file(
+        &self,
+        path: &Path,
+    ) -> Result<Option<String>, ExtractError> {
+        if self.raw {
+            return self.raw_docs(path, &read_source(path)?);
+        }
+        match self.read_file_docs(path)? {
+            Some(docs) => self.process_read_docs(path, &docs),
+            None => Ok(None),
+        }
+    }
+
+    /// Read and process the docs from many files.
+    ///
+    /// Files that aren’t cached are read in parallel, then docs are processed
+    /// in order. Each file gets its own result, so one bad file doesn’t hide
+    /// errors in the others.
+    #[must_use]
+    pub fn extract_files(
+        &self,
+        paths: &[PathBuf],
+    ) -> Vec<Result<Option<String>, ExtractError>> {
+        if self.raw {
+            return paths
+                .iter()
+                .zip(read_sources(paths))
+                .map(|(path, source)| self.raw_docs(path, &source?))
+                .collect();
+        }
+        let cached: Vec<_> = paths
+            .iter()
+            .map(|path| {
+                let key = cache::DocsKey::new(path, self);
+                let docs = key.as_ref().and_then(cache::get);
+                (key, docs)
+            })
+            .collect();
+        let uncached: Vec<PathBuf> = paths
+            .iter()
+            .zip(&cached)
+            .filter(|(_, (_, docs))| docs.is_none())
+            .map(|(path, _)| path.clone())
+            .collect();
+        let mut sources = read_sources(&uncached).into_iter();
+
+        paths
+            .iter()
+            .zip(cached)
+            .map(|(path, (key, docs))| {
+                let docs = if let Some(docs) = docs {
+                    docs
+                } else {
+                    let source =
+                        sources.next().unwrap_or_else(|| read_source(path))?;
+                    self.extract_and_cache(path, &source, key)?
+                };
+                match docs {
+                    Some(docs) => self.process_read_docs(path, &docs),
+                    None => Ok(None),
+                }
+            })
+            .collect()
+    }
+
+    /// Read, process, and combine the docs from many files, as the macros do.
+    ///
+    /// Each file’s docs are separated by [`DocExtractor::file_separator()`].
+    /// This is useful in build scripts, e.g. to write docs to a file in
+    /// `OUT_DIR` to be included with `include_str!` where the macros can’t be
+    /// used.
+    ///
+    /// # Errors
+    ///
+    /// Returns every error from [`DocExtractor::extract_files()`], if there
+    /// were any.
+    pub fn extract_combined(
+        &self,
+        paths: &[PathBuf],
+    ) -> Result<String, Vec<ExtractError>> {
+        let mut docs = Vec::new();
+        let mut errors = Vec::new();
+        for result in self.extract_files(paths) {
+            match result {
+                Ok(Some(file_docs)) => docs.push(file_docs),
+                Ok(None) => {}
+                Err(error) => errors.push(error),
+            }
+        }
+        if errors.is_empty() {
+            Ok(docs.join(self.file_separator()))
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Get the inner docs at the start of `source` exactly as written, for
+    /// `raw`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are no docs and `missing` is
+    /// [`Missing::Error`].
+    fn raw_docs(
+        &self,
+        path: &Path,
+        source: &str,
+    ) -> Result<Option<String>, ExtractError> {
+        let docs = scanner::leading_docs(scanner::strip_script_header(source));
+        if !docs.is_empty() {
+            Ok(Some(docs.to_owned()))
+        } else if self.missing == Missing::Error {
+            Err(ExtractError::EmptyDocs { path: path.to_owned() })
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Get the text to put between the docs of consecutive files.
+    ///
+    /// This is a blank line, except with `summary`, where each file’s docs are
+    /// a list item that should go on the next line.
+    #[must_use]
+    pub const fn file_separator(&self) -> &'static str {
+        if self.summary { "\n" } else { "\n\n" }
+    }
+
+    /// Process the docs extracted from a single file.
+    ///
+    /// Returns `None` if the file has no docs or has a `skip` directive. If
+    /// `link_items` is set, the file is read again to find its public items.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file had an invalid directive, no docs when
+    /// they’re required, or no owner when `owners` is set, or if `link_items`
+    /// is set and the file couldn’t be read.
+    pub fn process_file_docs(
+        &self,
+        path: &Path,
+        docs: &str,
+    ) -> Result<Option<String>, ExtractError> {
+        let item_links = if self.link_items {
+            self.item_links(scanner::strip_script_header(&read_source(path)?))
+        } else {
+            Vec::new()
+        };
+        self.process_read_docs(
+            path,
+            &FileDocs { docs: docs.to_owned(), item_links },
+        )
+    }
+
+    /// Process the docs read from a single file.
+    ///
+    /// Returns `None` if the file has no docs or has a `skip` directive.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file had an invalid directive, no docs when
+    /// they’re required, or no owner when `owners` is set.
+    fn process_read_docs(
+        &self,
+        path: &Path,
+        file_docs: &FileDocs,
+    ) -> Result<Option<String>, ExtractError> {
+        let (mut docs, directives) = directives::take(
+            &file_docs.docs,
+            self.audience.as_deref(),
+            self.lines.as_ref(),
+        )
+        .map_err(|message| ExtractError::invalid_docs(path, message))?;
+        if let Some((start, end)) = &self.between {
+            docs = markdown::between(&docs, start, end)
+                .map_err(|message| ExtractError::invalid_docs(path, message))?;
+        }
+        if directives.skip {
+            Ok(None)
+        } else if docs.trim().is_empty() {
+            match self.missing {
+                Missing::Allow => Ok(None),
+                Missing::Error => {
+                    Err(ExtractError::EmptyDocs { path: path.to_owned() })
+                }
+            }
+        } else {
+            let missing =
+                markdown::missing_sections(&docs, &self.require_sections);
+            if !missing.is_empty() {
+                return Err(ExtractError::MissingSections {
+                    path: path.to_owned(),
+                    headings: missing.into_iter().map(str::to_owned).collect(),
+                });
+            }
+            let owner = match &self.owners {
+                Some(owners) => Some(owners.owner(path).ok_or_else(|| {
+                    ExtractError::NoOwner { path: path.to_owned() }
+                })?),
+                None => None,
+            };
+            if let Some(assets) = &self.rewrite_assets {
+                docs = assets.rewrite(path, &docs);
+            }
+            if self.link_items {
+                docs = markdown::link_code_spans(&docs, &file_docs.item_links);
+            }
+            let docs = self.process_docs(&docs, &directives);
+            if self.summary && docs.is_empty() {
+                return Ok(None);
+            }
+            let file = path.file_name().unwrap_or_default();
+            let module = module_name(path);
+            #[expect(
+                clippy::literal_string_with_formatting_args,
+                reason = "`{file}`, `{module}`, and `{owner}` are placeholders \
+                    in the prefix, suffix, and heading suffix"
+            )]
+            let surround = |text: &str| {
+                let text = text
+                    .replace("{file}", &file.to_string_lossy())
+                    .replace("{module}", &module);
+                match owner {
+                    Some(owner) => text.replace("{owner}", owner),
+                    None => text,
+                }
+            };
+            let docs = if self.heading_suffix.is_empty() {
+                docs
+            } else {
+                markdown::suffix_headings(
+                    &docs,
+                    &surround(&self.heading_suffix),
+                )
+            };
+            Ok(Some(format!(
+                "{}{docs}{}",
+                surround(&self.prefix),
+                surround(&self.suffix),
+            )))
+        }
+    }
+
+    /// Apply transformations from `self` and `directives` to docs.
+    ///
+    /// This doesn’t add the prefix or suffix, which are per file.
+    #[must_use]
+    pub fn process_docs(&self, docs: &str, directives: &Directives) -> String {
+        let mut docs = if self.trim {
+            markdown::dedent(docs)
+        } else {
+            docs.to_owned()
+        };
+        if let Some(patterns) = &self.strip_license {
+            docs = markdown::strip_license(&docs, patterns);
+        }
+        docs = markdown::exclude_sections(&docs, &self.exclude_sections);
+        docs = markdown::select_sections(&docs, &self.sections);
+        docs = markdown::rewrite_doctests(&docs, &self.rewrite_doctests);
+        if let Some(attribute) = self.doctests.attribute() {
+            docs = markdown::set_doctest_attribute(&docs, attribute);
+        }
+        if let Some(title) = &directives.title {
+            docs = markdown::set_title(&docs, title);
+        }
+        docs = markdown::shift_headings(
+            &docs,
+            directives.shift_headings.unwrap_or(self.shift_headings),
+        );
+        if self.summary {
+            docs = markdown::first_paragraph(&docs)
+                .map_or_else(String::new, |summary| format!("* {summary}"));
+        }
+        docs = match self.escape {
+            Escape::None => docs,
+            Escape::Html => markdown::escape_html(&docs),
+            Escape::Links => markdown::escape_unresolved_links(&docs),
+            Escape::All => {
+                markdown::escape_html(&markdown::escape_unresolved_links(&docs))
+            }
+        };
+        if self.collapse_blank_lines {
+            docs = markdown::collapse_blank_lines(&docs);
+        }
+        match self.format {
+            Format::Markdown => {
+                let docs = markdown::pre_blocks(&docs, &self.pre_blocks);
+                if directives.collapse {
+                    markdown::collapse(&docs)
+                } else {
+                    docs
+                }
+            }
+            Format::Text => text::render(&docs),
+            Format::Ansi => text::render_ansi(&docs),
+        }
+    }
+
+    /// Read inner doc comments from a Rust source file without processing
+    /// them.
+    ///
+    /// Docs are cached, so the file is only parsed again if it has changed.
+    /// Returns `None` if the file is hidden with `#![doc(hidden)]` and `hidden`
+    /// is [`Hidden::Skip`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file couldn’t be read or parsed.
+    pub fn read_docs(
+        &self,
+        path: &Path,
+    ) -> Result<Option<String>, ExtractError> {
+        Ok(self.read_file_docs(path)?.map(|file_docs| file_docs.docs))
+    }
+
+    /// Read inner doc comments from a Rust source file, and links to its
+    /// public items if `link_items` is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file couldn’t be read or parsed.
+    fn read_file_docs(
+        &self,
+        path: &Path,
+    ) -> Result<Option<FileDocs>, ExtractError> {
+        let key = cache::DocsKey::new(path, self);
+        if let Some(docs) = key.as_ref().and_then(cache::get) {
+            return Ok(docs);
+        }
+        self.extract_and_cache(path, &read_source(path)?, key)
+    }
+
+    /// Extract inner doc comments from `source`, and cache them under `key`.
+    ///
+    /// Returns `None` if the file is hidden and hidden files are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source couldn’t be parsed.
+    fn extract_and_cache(
+        &self,
+        path: &Path,
+        source: &str,
+        key: Option<cache::DocsKey>,
+    ) -> Result<Option<FileDocs>, ExtractError> {
+        let (docs, included) = match self.extract_docs(path, source)? {
+            Some((docs, included)) => (Some(docs), included),
+            None => (None, Vec::new()),
+        };
+        if let Some(key) = key {
+            cache::insert(key, docs.clone(), &included);
+        }
+        Ok(docs)
+    }
+
+    /// Extract inner doc comments from the source of the file at `path`.
+    ///
+    /// Files included with `#![doc = include_str!(...)]` are read, too, and
+    /// their paths are returned with the docs. Returns `None` if the file, or
+    /// the inline module if `module` is set, is hidden and hidden files are
+    /// skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source couldn’t be parsed, if `module` wasn’t
+    /// found, or if an included file couldn’t be read.
+    fn extract_docs(
+        &self,
+        path: &Path,
+        source: &str,
+    ) -> Result<Option<(FileDocs, Vec<PathBuf>)>, ExtractError> {
+        let source = scanner::strip_script_header(source);
+        let attrs = match &self.module {
+            Some(module) => self.module_attrs(path, source, module)?,
+            None => self.file_attrs(path, source)?,
+        };
+        let Some(attrs) = attrs else {
+            return Ok(None);
+        };
+        let (docs, included) = includes::docs(&attrs, path)?;
+        let item_links = self.item_links(source);
+        Ok(Some((FileDocs { docs, item_links }, included)))
+    }
+
+    /// Get intra-doc links to the public items in `source`, if `link_items`
+    /// is set.
+    ///
+    /// If `module` is set, only items in that inline module are linked.
+    /// Nothing is linked if the source can’t be parsed, which can only happen
+    /// if `lenient` is set.
+    fn item_links(&self, source: &str) -> Vec<String> {
+        if !self.link_items {
+            return Vec::new();
+        }
+        let Ok(file) = syn::parse_file(source) else {
+            return Vec::new();
+        };
+        let items = match &self.module {
+            Some(module) => {
+                items::inline_module_items(&file, module).unwrap_or_default()
+            }
+            None => &file.items,
+        };
+        items::public_items(items)
+            .into_iter()
+            .map(|item| item.link)
+            .collect()
+    }
+
+    /// Get the inner attributes at the top of `source`, or anywhere in it if
+    /// `anywhere` is set.
+    ///
+    /// Returns `None` if the file is hidden and hidden files are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source couldn’t be parsed, or, if `lenient` is
+    /// set, couldn’t even be tokenized.
+    fn file_attrs(
+        &self,
+        path: &Path,
+        source: &str,
+    ) -> Result<Option<Vec<syn::Attribute>>, ExtractError> {
+        if self.hidden == Hidden::Skip && scanner::is_hidden(source) {
+            return Ok(None);
+        }
+        let source = match self.blocks {
+            Blocks::All => source,
+            Blocks::First => scanner::first_doc_block(source),
+        };
+        let source = scanner::only_styles(source, &self.styles);
+        if self.anywhere {
+            scanner::inner_attrs_anywhere(&source)
+        } else {
+            match syn::parse_file(&source) {
+                Ok(file) => Ok(file.attrs),
+                Err(error) if self.lenient => {
+                    scanner::leading_inner_attrs(&source)
+                        .map_err(|_| error.to_string())
+                }
+                Err(error) => Err(error.to_string()),
+            }
+        }
+        .map(Some)
+        .map_err(|message| ExtractError::Parse {
+            path: path.to_owned(),
+            message,
+        })
+    }
+
+    /// Get the inner attributes of the inline module `module` in `source`.
+    ///
+    /// Returns `None` if the module is hidden and hidden files are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the source couldn’t be parsed, or if the module
+    /// wasn’t found.
+    fn module_attrs(
+        &self,
+        path: &Path,
+        source: &str,
+        module: &str,
+    ) -> Result<Option<Vec<syn::Attribute>>, ExtractError> {
+        let source = scanner::only_styles(source, &self.styles);
+        let file =
+            syn::parse_file(&source).map_err(|error| ExtractError::Parse {
+                path: path.to_owned(),
+                message: error.to_string(),
+            })?;
+        let attrs =
+            items::inline_module_attrs(&file, module).ok_or_else(|| {
+                ExtractError::ModuleNotFound {
+                    path: path.to_owned(),
+                    name: module.to_owned(),
+                }
+            })?;
+        if self.hidden == Hidden::Skip && items::is_hidden(&attrs) {
+            Ok(None)
+        } else {
+            Ok(Some(attrs))
+        }
+    }
+}
+
+/// Docs read from a file, before processing.
+#[derive(Clone, Debug)]
+struct FileDocs {
+    /// The file’s inner doc comments.
+    docs: String,
+
+    /// Intra-doc links to the file’s public items, if `link_items` is set.
+    item_links: Vec<String>,
+}
+
+/// Which blocks of inner doc comments to read from a file.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Blocks {
+    /// Read all inner doc comments.
+    #[default]
+    All,
+
+    /// Only read the first contiguous block of inner doc comments.
+    First,
+}
+
+/// How to treat Rust code blocks that would be run as doctests.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Doctests {
+    /// Leave code blocks alone.
+    #[default]
+    Test,
+
+    /// Mark code blocks `ignore`.
+    Ignore,
+
+    /// Mark code blocks `no_run`.
+    NoRun,
+}
+
+impl Doctests {
+    /// Get the code block attribute to add, if any.
+    #[must_use]
+    pub const fn attribute(self) -> Option<&'static str> {
+        match self {
+            Self::Test => None,
+            Self::Ignore => Some("ignore"),
+            Self::NoRun => Some("no_run"),
+        }
+    }
+}
+
+/// How to escape special characters in the output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Escape {
+    /// Don’t escape anything.
+    #[default]
+    None,
+
+    /// Escape `<`, `>`, and `&` outside of code as HTML entities.
+    Html,
+
+    /// Escape brackets outside of code that `rustdoc` would treat as broken
+    /// intra-doc links.
+    Links,
+
+    /// Escape both HTML and brackets, as with [`Escape::Html`] and
+    /// [`Escape::Links`].
+    All,
+}
+
+/// The format to produce docs in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Markdown, as used by `rustdoc`.
+    #[default]
+    Markdown,
+
+    /// Plain text without Markdown markup, e.g. for command line help.
+    Text,
+
+    /// Text like [`Format::Text`], styled with ANSI escape codes for display
+    /// in a terminal.
+    Ansi,
+}
+
+/// How inner docs are written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DocStyle {
+    /// `//!` comments.
+    Line,
+
+    /// `/*! ... */` comments.
+    Block,
+
+    /// `#![doc = "..."]` attributes.
+    Attribute,
+}
+
+/// What to do with files marked `#![doc(hidden)]`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Hidden {
+    /// Include the file’s docs like any other file’s.
+    #[default]
+    Include,
+
+    /// Skip the file.
+    Skip,
+}
+
+/// What to do if a file has no docs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Missing {
+    /// Silently skip the file.
+    #[default]
+    Allow,
+
+    /// Fail with an error.
+    Error,
+}
+
+/// Get the name of the module in the file at `path`.
+///
+/// This is the file’s stem, e.g. `apple` for `apple.rs`, or the name of its
+/// directory for `mod.rs`-style files, e.g. `fruit` for `fruit/mod.rs`.
+fn module_name(path: &Path) -> String {
+    let path = if discover::ModuleFile::new(path.to_owned()).is_mod_rs {
+        path.parent().unwrap_or(path)
+    } else {
+        path
+    };
+    path.file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Read source files in parallel.
+///
+/// Results are in the same order as `paths`.
+fn read_sources(paths: &[PathBuf]) -> Vec<Result<String, ExtractError>> {
+    let threads = thread::available_parallelism().map_or(1, usize::from);
+    let chunk_size = paths.len().div_ceil(threads).max(1);
+    thread::scope(|scope| {
+        paths
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(|| {
+                    chunk
+                        .iter()
+                        .map(|path| read_source(path))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .flat_map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect::<Vec<_>>()
+    })
+}
+
+/// Paths passed to [`read_source()`] since [`take_read_paths()`] was called.
+static READ_PATHS: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+/// Read a source file.
+///
+/// As with `rustc`, a leading byte order mark is removed and CRLF line endings
+/// are converted to LF, so that files saved on Windows don’t leave stray `\r`
+/// characters in docs.
+///
+/// The path is recorded for [`take_read_paths()`], even if the file couldn’t
+/// be read.
+///
+/// # Errors
+///
+/// Returns an error that includes the path if the file couldn’t be read or
+/// isn’t valid UTF-8.
+pub fn read_source(path: &Path) -> Result<String, ExtractError> {
+    record_read_path(path);
+    fs::read_to_string(path)
+        .map(normalize_source)
+        .map_err(|error| ExtractError::Io { path: path.to_owned(), error })
+}
+
+/// Record that `path` was read, for [`take_read_paths()`].
+///
+/// This is also used when docs read from `path` come from the cache.
+fn record_read_path(path: &Path) {
+    READ_PATHS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .push(path.to_owned());
+}
+
+/// Remove a leading byte order mark and convert CRLF line endings to LF.
+fn normalize_source(source: String) -> String {
+    let source = match source.strip_prefix('\u{feff}') {
+        Some(rest) => rest.to_owned(),
+        None => source,
+    };
+    if source.contains("\r\n") {
+        source.replace("\r\n", "\n")
+    } else {
+        source
+    }
+}
+
+/// Read and parse a Rust source file.
+///
+/// # Errors
+///
+/// Returns an error if the file couldn’t be read or parsed.
+pub fn parse_source(path: &Path) -> Result<syn::File, ExtractError> {
+    syn::parse_file(&read_source(path)?).map_err(|error| ExtractError::Parse {
+        path: path.to_owned(),
+        message: error.to_string(),
+    })
+}
+
+/// Get the paths of files read since the last call, and forget them.
+///
+/// This includes every file read by [`read_source()`], which is used for all
+/// files read while extracting docs. The macros use this to tell the compiler
+/// which files their output depends on.
+///
+/// Files with cached docs aren’t read again, so they might not be returned by
+/// a later call.
+#[must_use]
+pub fn take_read_paths() -> Vec<PathBuf> {
+    std::mem::take(
+        &mut *READ_PATHS.lock().unwrap_or_else(PoisonError::into_inner),
+    )
+}
+
+/// Extract inner doc comments from Rust source.
+///
+/// Since there’s no path to resolve it against, `#![doc = include_str!(...)]`
+/// is skipped. [`DocExtractor`] reads included files.
+///
+/// # Errors
+///
+/// Returns an error if there was a problem parsing the file.
+pub fn extract_inner_docs(content: &str) -> Result<String, String> {
+    syn::parse_file(content)
+        .map(|file| items::docs(&file.attrs))
+        .map_err(|error| error.to_string())
+}
+
+/// Get the path to a test fixture.
+#[cfg(test)]
+fn fixture(path: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../tests")
+        .join(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::assert;
+
+    #[test]
+    fn normalize_windows_source() {
+        assert!(
+            normalize_source("\u{feff}//! A\r\n//!\r\n//! B\r\n".to_owned())
+                == "//! A\n//!\n//! B\n"
+        );
+        assert!(normalize_source("a\rb\n".to_owned()) == "a\rb\n");
+    }
+
+    #[test]
+    fn line_doc_comments() {
+        assert!(
+            extract_inner_docs(
+                r"
+//! First line
+//! Second line
+
+fn foo() {}
+"
+            )
+            .unwrap()
+                == " First line\n Second line"
+        );
+    }
+
+    #[test]
+    fn mixed_attrs() {
+        assert!(
+            extract_inner_docs(
+                r"
+//! First line
+#![forbid(unsafe_code)]
+//! Second line
+
+fn foo() {}
+"
+            )
+            .unwrap()
+                == " First line\n Second line"
+        );
+    }
+
+    #[test]
+    fn block_doc_comments() {
+        assert!(
+            extract_inner_docs(
+                r"
+/*! Block doc comment
+with multiple lines
+*/
+
+fn foo() {}
+"
+            )
+            .unwrap()
+                == " Block doc comment\nwith multiple lines\n"
+        );
+    }
+
+    #[test]
+    fn doc_attributes() {
+        assert!(
+            extract_inner_docs(
+                r#"
+#![doc = "First line"]
+#![doc = "Second line"]
+
+fn foo() {}
+"#
+            )
+            .unwrap()
+                == "First line\nSecond line"
+        );
+    }
+
+    #[test]
+    fn mixed_doc_styles() {
+        assert!(
+            extract_inner_docs(
+                r#"
+//! Line comment
+#![doc = "Attribute doc"]
+
+fn foo() {}
+"#
+            )
+            .unwrap()
+                == " Line comment\nAttribute doc"
+        );
+    }
+
+    #[test]
+    fn no_docs() {
+        assert!(extract_inner_docs("fn foo() {}\n").unwrap() == "");
+    }
+
+    #[test]
+    fn only_outer_docs_ignored() {
+        assert!(
+            extract_inner_docs(
+                r"
+/// This is an outer doc comment
+fn foo() {}
+"
+            )
+            .unwrap()
+                == ""
+        );
+    }
+
+    #[test]
+    fn realistic_module() {
+        assert!(
+            extract_inner_docs(
+                r"
+//! # Module Title
+//!
+//! This module does things.
+
+use std::io;
+
+/// Function doc
+pub fn do_thing() {}
+"
+            )
+            .unwrap()
+                == " # Module Title\n\n This module does things."
+        );
+    }
+
+    #[test]
+    fn empty_doc_lines() {
+        assert!(
+            extract_inner_docs(
+                r"
+//! First
+//!
+//! Third
+
+fn foo() {}
+"
+            )
+            .unwrap()
+                == " First\n\n Third"
+        );
+    }
+
+    #[test]
+    fn extract_file() {
+        let path = fixture("fruit/apple.rs");
+        let extractor = DocExtractor {
+            shift_headings: 1,
+            trim: true,
+            prefix: "From {file}:\n\n".to_owned(),
+            ..DocExtractor::default()
+        };
+        assert!(
+            extractor.extract_file(&path).unwrap().as_deref()
+                == Some(
+                    "From apple.rs:\n\n### Apple processing\n\n\
+                    Green or red, we don't care."
+                )
+        );
+    }
+
+    #[test]
+    fn extract_combined() {
+        let extractor =
+            DocExtractor { summary: true, ..DocExtractor::default() };
+        assert!(
+            extractor
+                .extract_combined(&[
+                    fixture("fruit/apple.rs"),
+                    fixture("doc_formats/no_docs.rs"),
+                    fixture("fruit/orange.rs"),
+                ])
+                .unwrap()
+                == "* Green or red, we don't care.\n\
+                * Various orange-related code."
+        );
+        let errors = extractor
+            .extract_combined(&[fixture("nonesuch.rs"), fixture("nope.rs")])
+            .unwrap_err();
+        assert!(errors.len() == 2);
+    }
+
+    #[test]
+    fn extract_files_missing() {
+        let extractor =
+            DocExtractor { missing: Missing::Error, ..DocExtractor::default() };
+        let results = extractor.extract_files(&[
+            fixture("fruit/apple.rs"),
+            fixture("doc_formats/no_docs.rs"),
+            fixture("nonesuch.rs"),
+        ]);
+        assert!(let Ok(Some(_)) = &results[0]);
+        assert!(let Err(ExtractError::EmptyDocs { .. }) = &results[1]);
+        assert!(let Err(ExtractError::Io { .. }) = &results[2]);
+    }
+
+    #[test]
+    fn read_paths() {
+        let path = fixture("fruit/orange.rs");
+        assert!(let Ok(_) = read_source(&path));
+        assert!(take_read_paths().contains(&path));
+    }
+}
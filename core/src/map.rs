@@ -0,0 +1,105 @@
+//! Generate a Markdown map of the modules in a directory tree.
+
+use crate::markdown::{Fences, LineKind, parse_heading};
+
+/// A module to list in the map.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ModuleEntry {
+    /// Path to the module relative to the root, e.g. `fruit::apple`.
+    pub path: String,
+
+    /// Directory containing the module file relative to the root, e.g.
+    /// `fruit`. This is empty for files in the root directory.
+    pub dir: String,
+
+    /// Summary of the module’s docs.
+    pub summary: Option<String>,
+}
+
+/// Get a one line summary of `docs`.
+///
+/// This is the first line of text that isn’t a heading or in a code block.
+pub fn summary(docs: &str) -> Option<String> {
+    let mut fences = Fences::default();
+    docs.split('\n')
+        .filter(|line| fences.classify(line) == LineKind::Text)
+        .map(str::trim)
+        .find(|line| !line.is_empty() && parse_heading(line).is_none())
+        .map(str::to_owned)
+}
+
+/// Generate a Markdown map of `modules`, grouped by directory.
+///
+/// Modules in the root directory are listed first without a heading. Each
+/// other directory gets a heading followed by its modules. Directories are
+/// listed in the order they first appear in `modules`.
+#[must_use]
+pub fn render(modules: &[ModuleEntry]) -> String {
+    let mut dirs: Vec<&str> = Vec::new();
+    for module in modules {
+        if !dirs.contains(&module.dir.as_str()) {
+            dirs.push(&module.dir);
+        }
+    }
+    dirs.sort_by_key(|dir| !dir.is_empty());
+
+    dirs.iter()
+        .map(|dir| {
+            let list = modules
+                .iter()
+                .filter(|module| module.dir == *dir)
+                .map(|module| match &module.summary {
+                    Some(summary) => {
+                        format!("* [`{}`]: {summary}", module.path)
+                    }
+                    None => format!("* [`{}`]", module.path),
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            if dir.is_empty() {
+                list
+            } else {
+                format!("# `{dir}/`\n\n{list}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::assert;
+
+    #[test]
+    fn summary_skips_headings() {
+        assert!(
+            summary(" # Title\n\n ```\n code\n ```\n First line.\n Second.")
+                == Some("First line.".to_owned())
+        );
+        assert!(summary(" # Title") == None);
+    }
+
+    /// Create a `ModuleEntry`.
+    fn entry(path: &str, dir: &str, summary: Option<&str>) -> ModuleEntry {
+        ModuleEntry {
+            path: path.to_owned(),
+            dir: dir.to_owned(),
+            summary: summary.map(str::to_owned),
+        }
+    }
+
+    #[test]
+    fn grouped_by_directory() {
+        assert!(
+            render(&[
+                entry("roots", "roots", Some("Roots.")),
+                entry("roots::tip", "roots", None),
+                entry("trunk", "", Some("Trunk.")),
+                entry("trunk::branch", "trunk", Some("Branch.")),
+            ]) == "* [`trunk`]: Trunk.\n\n\
+                # `roots/`\n\n* [`roots`]: Roots.\n* [`roots::tip`]\n\n\
+                # `trunk/`\n\n* [`trunk::branch`]: Branch."
+        );
+    }
+}
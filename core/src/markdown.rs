@@ -0,0 +1,1489 @@
+//! Process Markdown found in doc comments.
+
+/// Where a line of Markdown is relative to fenced code blocks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LineKind {
+    /// Regular Markdown outside of any code block.
+    Text,
+
+    /// The line opening a fenced code block.
+    FenceOpen {
+        /// Whether `rustdoc` will treat the code block as Rust.
+        is_rust: bool,
+    },
+
+    /// A line inside a fenced code block.
+    Code {
+        /// Whether `rustdoc` will treat the code block as Rust.
+        is_rust: bool,
+    },
+
+    /// The line closing a fenced code block.
+    FenceClose,
+}
+
+/// Tracks fenced code blocks across lines of Markdown.
+#[derive(Debug, Default)]
+pub struct Fences {
+    /// The currently open fence, if any.
+    open: Option<Fence>,
+}
+
+/// An open code fence.
+#[derive(Debug)]
+struct Fence {
+    /// The character used for the fence: ``'`'`` or `'~'`.
+    marker: char,
+
+    /// The number of marker characters in the opening fence.
+    len: usize,
+
+    /// Whether `rustdoc` will treat the code block as Rust.
+    is_rust: bool,
+}
+
+impl Fences {
+    /// Determine what kind of line `line` is, and update the state.
+    ///
+    /// This must be called on every line in order.
+    pub fn classify(&mut self, line: &str) -> LineKind {
+        let fence = parse_fence(line);
+        match (&self.open, fence) {
+            (Some(open), Some((marker, len, info)))
+                if marker == open.marker
+                    && len >= open.len
+                    && info.is_empty() =>
+            {
+                self.open = None;
+                LineKind::FenceClose
+            }
+            (Some(open), _) => LineKind::Code { is_rust: open.is_rust },
+            (None, Some((marker, len, info))) => {
+                let is_rust = is_rust_info(info);
+                self.open = Some(Fence { marker, len, is_rust });
+                LineKind::FenceOpen { is_rust }
+            }
+            (None, None) => LineKind::Text,
+        }
+    }
+
+    /// Get a line that closes the currently open fence, if any.
+    #[must_use]
+    pub fn closing(&self) -> Option<String> {
+        self.open
+            .as_ref()
+            .map(|open| open.marker.to_string().repeat(open.len))
+    }
+}
+
+/// Replace each line of `docs` with the result of `f(line, kind)`.
+pub fn map_lines<F>(docs: &str, mut f: F) -> String
+where
+    F: FnMut(&str, LineKind) -> String,
+{
+    let mut fences = Fences::default();
+    docs.split('\n')
+        .map(|line| f(line, fences.classify(line)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Parse a code fence line into its marker, length, and info string.
+///
+/// Leading whitespace is ignored since doc comments are usually indented by a
+/// space, and `rustdoc` removes common indentation anyway.
+fn parse_fence(line: &str) -> Option<(char, usize, &str)> {
+    let line = line.trim_start();
+    let marker = line.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let info = line.trim_start_matches(marker);
+    let len = line.len().checked_sub(info.len())?;
+    if len < 3 || (marker == '`' && info.contains('`')) {
+        return None;
+    }
+    Some((marker, len, info.trim()))
+}
+
+/// Determine if `rustdoc` will treat a code block with `info` as Rust.
+///
+/// Code blocks are Rust if they have no language, are explicitly `rust`, or
+/// only have `rustdoc` attributes like `ignore`.
+#[must_use]
+pub fn is_rust_info(info: &str) -> bool {
+    let mut seen_other = false;
+    for token in info
+        .split([',', ' ', '\t'])
+        .map(|token| token.trim_matches(['{', '}', '.']))
+        .filter(|token| !token.is_empty())
+    {
+        if token == "rust" || is_rustdoc_attribute(token) {
+            return true;
+        }
+        seen_other = true;
+    }
+    !seen_other
+}
+
+/// Determine if `token` is a code block attribute understood by `rustdoc`.
+fn is_rustdoc_attribute(token: &str) -> bool {
+    matches!(
+        token,
+        "ignore"
+            | "should_panic"
+            | "no_run"
+            | "compile_fail"
+            | "test_harness"
+            | "standalone_crate"
+    ) || token.starts_with("ignore-")
+        || token.starts_with("edition")
+        || (token.len() == 5
+            && token.starts_with('E')
+            && token[1..].chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Add `attribute` (e.g. `ignore`) to every Rust code block.
+///
+/// Code blocks that already have the attribute are left alone.
+#[must_use]
+pub fn set_doctest_attribute(docs: &str, attribute: &str) -> String {
+    map_lines(docs, |line, kind| match kind {
+        LineKind::FenceOpen { is_rust: true } => {
+            add_fence_attribute(line, attribute)
+        }
+        _ => line.to_owned(),
+    })
+}
+
+/// Add `attribute` to the info string of the opening fence in `line`.
+fn add_fence_attribute(line: &str, attribute: &str) -> String {
+    let info = line.trim_start().trim_start_matches(['`', '~']);
+    let fence = &line[..line.len().saturating_sub(info.len())];
+    let info = info.trim();
+    if info
+        .split([',', ' ', '\t'])
+        .any(|token| token == attribute || token.starts_with("ignore"))
+    {
+        line.to_owned()
+    } else if info.is_empty() {
+        format!("{fence}{attribute}")
+    } else {
+        format!("{fence}{info},{attribute}")
+    }
+}
+
+/// Add `language` to code blocks that don’t have an info string.
+///
+/// Plain Markdown code blocks without a language usually aren’t Rust, but
+/// `rustdoc` treats them as Rust and runs them as doctests.
+#[must_use]
+pub fn label_plain_fences(docs: &str, language: &str) -> String {
+    map_lines(docs, |line, kind| match (kind, parse_fence(line)) {
+        (LineKind::FenceOpen { .. }, Some((_, _, ""))) => {
+            format!("{}{language}", line.trim_end())
+        }
+        _ => line.to_owned(),
+    })
+}
+
+/// Remove the leading whitespace common to every non-blank line.
+///
+/// `//! Foo` comments produce lines like `" Foo"`. Removing only the common
+/// indentation keeps the relative indentation of code blocks and lists.
+pub fn dedent(docs: &str) -> String {
+    /// Count the leading spaces and tabs in `line`.
+    fn indent(line: &str) -> usize {
+        line.len()
+            .saturating_sub(line.trim_start_matches([' ', '\t']).len())
+    }
+
+    let common = docs
+        .split('\n')
+        .filter(|line| !line.trim().is_empty())
+        .map(indent)
+        .min()
+        .unwrap_or(0);
+    docs.split('\n')
+        .map(|line| line.get(common.min(indent(line))..).unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Escape brackets that `rustdoc` would treat as broken intra-doc links.
+///
+/// Shortcut links like `[text]` are escaped unless a reference definition for
+/// them exists in `docs`. Inline links like `[text](url)`, full reference links
+/// like `[text][ref]`, and brackets in code are left alone.
+#[must_use]
+pub fn escape_unresolved_links(docs: &str) -> String {
+    let mut fences = Fences::default();
+    let definitions: Vec<String> = docs
+        .split('\n')
+        .filter(|line| fences.classify(line) == LineKind::Text)
+        .filter_map(|line| {
+            let (label, _) =
+                line.trim_start().strip_prefix('[')?.split_once("]:")?;
+            Some(label.to_lowercase())
+        })
+        .collect();
+
+    map_lines(docs, |line, kind| match kind {
+        LineKind::Text => map_outside_code_spans(line, |text| {
+            escape_shortcut_links(text, &definitions)
+        }),
+        _ => line.to_owned(),
+    })
+}
+
+/// Escape shortcut links in `text` whose labels aren’t in `definitions`.
+fn escape_shortcut_links(text: &str, definitions: &[String]) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some((before, after_open)) = rest.split_once('[') {
+        result.push_str(before);
+        let Some((label, after_close)) = after_open.split_once(']') else {
+            result.push('[');
+            rest = after_open;
+            continue;
+        };
+        let is_link = before.ends_with(['\\', '!'])
+            || after_close.starts_with(['(', '[', ':'])
+            || definitions.contains(&label.to_lowercase());
+        if is_link {
+            result.push('[');
+            result.push_str(label);
+            result.push(']');
+            // Skip the reference in a full reference link like `[text][ref]`.
+            if let Some(reference) = after_close.strip_prefix('[')
+                && let Some((reference, after_reference)) =
+                    reference.split_once(']')
+            {
+                result.push('[');
+                result.push_str(reference);
+                result.push(']');
+                rest = after_reference;
+                continue;
+            }
+        } else {
+            result.push_str("\\[");
+            result.push_str(label);
+            result.push_str("\\]");
+        }
+        rest = after_close;
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Parse an ATX heading (e.g. `## Title`) into its level and text.
+///
+/// Returns `None` if `line` isn’t a heading.
+#[must_use]
+pub fn parse_heading(line: &str) -> Option<(usize, &str)> {
+    let trimmed = line.trim_start();
+    let text = trimmed.trim_start_matches('#');
+    let level = trimmed.len().checked_sub(text.len())?;
+    if (1..=6).contains(&level)
+        && (text.is_empty() || text.starts_with([' ', '\t']))
+    {
+        Some((level, text.trim()))
+    } else {
+        None
+    }
+}
+
+/// Remove an optional closing sequence of `#` from heading text.
+pub(crate) fn strip_closing(text: &str) -> &str {
+    let without = text.trim_end_matches('#');
+    if without.is_empty() || without.ends_with([' ', '\t']) {
+        without.trim_end()
+    } else {
+        text
+    }
+}
+
+/// Add `suffix` to the text of every heading outside of code blocks.
+///
+/// Headings without text are left alone.
+#[must_use]
+pub fn suffix_headings(docs: &str, suffix: &str) -> String {
+    map_lines(docs, |line, kind| match (kind, parse_heading(line)) {
+        (LineKind::Text, Some((level, text)))
+            if !strip_closing(text).is_empty() =>
+        {
+            format!(
+                "{}{} {}{suffix}",
+                indent(line),
+                "#".repeat(level),
+                strip_closing(text)
+            )
+        }
+        _ => line.to_owned(),
+    })
+}
+
+/// Increase the level of every heading outside of code blocks by `shift`.
+///
+/// Headings are never made deeper than level 6.
+#[must_use]
+pub fn shift_headings(docs: &str, shift: usize) -> String {
+    if shift == 0 {
+        return docs.to_owned();
+    }
+    map_lines(docs, |line, kind| match (kind, parse_heading(line)) {
+        (LineKind::Text, Some((level, _))) => {
+            let trimmed = line.trim_start();
+            let indent = line.strip_suffix(trimmed).unwrap_or("");
+            let rest = trimmed.trim_start_matches('#');
+            let level = level.saturating_add(shift).min(6);
+            format!("{indent}{}{rest}", "#".repeat(level))
+        }
+        _ => line.to_owned(),
+    })
+}
+
+/// Prefixes of lines that mark a paragraph as a license header.
+const LICENSE_PREFIXES: [&str; 4] = [
+    "SPDX-License-Identifier:",
+    "SPDX-FileCopyrightText:",
+    "Copyright",
+    "Licensed under",
+];
+
+/// Remove license header paragraphs from the start of `docs`.
+///
+/// A paragraph is a license header if any of its lines starts with an SPDX
+/// tag, “Copyright”, or “Licensed under”, or contains one of `patterns`.
+/// Paragraphs are removed until one doesn’t look like a license header.
+#[must_use]
+pub fn strip_license(docs: &str, patterns: &[String]) -> String {
+    let is_license_line = |line: &str| {
+        let line = line.trim();
+        LICENSE_PREFIXES
+            .iter()
+            .any(|prefix| line.starts_with(prefix))
+            || patterns
+                .iter()
+                .any(|pattern| line.contains(pattern.as_str()))
+    };
+
+    let all_lines: Vec<&str> = docs.split('\n').collect();
+    let mut lines = all_lines.as_slice();
+    let mut stripped = false;
+    loop {
+        lines = skip_blank_lines(lines);
+        let len = lines
+            .iter()
+            .position(|line| line.trim().is_empty())
+            .unwrap_or(lines.len());
+        let (paragraph, after) = lines.split_at(len);
+        if paragraph.is_empty()
+            || paragraph.iter().any(|line| parse_fence(line).is_some())
+            || !paragraph.iter().any(|line| is_license_line(line))
+        {
+            break;
+        }
+        lines = after;
+        stripped = true;
+    }
+
+    if stripped {
+        lines.join("\n")
+    } else {
+        docs.to_owned()
+    }
+}
+
+/// Skip blank lines at the start of `lines`.
+fn skip_blank_lines<'a>(lines: &'a [&'a str]) -> &'a [&'a str] {
+    let start = lines
+        .iter()
+        .position(|line| !line.trim().is_empty())
+        .unwrap_or(lines.len());
+    lines.get(start..).unwrap_or_default()
+}
+
+/// Remove sections with headings named in `names` outside of code blocks.
+///
+/// A section runs from its heading up to the next heading of the same or a
+/// higher level, so its subsections are removed too. Heading text must match
+/// exactly, ignoring surrounding whitespace.
+#[must_use]
+pub fn exclude_sections(docs: &str, names: &[String]) -> String {
+    if names.is_empty() {
+        return docs.to_owned();
+    }
+
+    let mut fences = Fences::default();
+    let mut excluding: Option<usize> = None;
+    let mut excluded_any = false;
+    let mut lines = Vec::new();
+    for line in docs.split('\n') {
+        let kind = fences.classify(line);
+        if kind == LineKind::Text
+            && let Some((level, text)) = parse_heading(line)
+        {
+            if excluding.is_some_and(|excluded| level <= excluded) {
+                excluding = None;
+            }
+            if excluding.is_none() && names.iter().any(|name| name == text) {
+                excluding = Some(level);
+                excluded_any = true;
+            }
+        }
+        if excluding.is_none() {
+            lines.push(line);
+        }
+    }
+
+    if excluded_any {
+        while lines.last().is_some_and(|line| line.trim().is_empty()) {
+            lines.pop();
+        }
+    }
+    lines.join("\n")
+}
+
+/// Keep only the sections with headings matching `selectors`.
+///
+/// Selectors are heading names that may contain `*` (any text) and `?` (any
+/// single character). A selector starting with `!` removes matching sections
+/// instead, even within selected sections. If there are no positive selectors,
+/// every section is kept except those removed; otherwise, only selected
+/// sections are kept and any text before the first heading is dropped.
+///
+/// As with [`exclude_sections()`], a section includes its subsections.
+pub fn select_sections(docs: &str, selectors: &[String]) -> String {
+    if selectors.is_empty() {
+        return docs.to_owned();
+    }
+    let (remove, keep): (Vec<&str>, Vec<&str>) = selectors
+        .iter()
+        .map(String::as_str)
+        .partition(|selector| selector.starts_with('!'));
+    let remove: Vec<&str> = remove
+        .iter()
+        .filter_map(|selector| selector.strip_prefix('!'))
+        .collect();
+    let matches = |patterns: &[&str], text: &str| {
+        patterns.iter().any(|pattern| wildcard_match(pattern, text))
+    };
+
+    let mut fences = Fences::default();
+    let mut keeping: Option<usize> = None;
+    let mut removing: Option<usize> = None;
+    let mut lines = Vec::new();
+    for line in docs.split('\n') {
+        if fences.classify(line) == LineKind::Text
+            && let Some((level, text)) = parse_heading(line)
+        {
+            if removing.is_some_and(|removing| level <= removing) {
+                removing = None;
+            }
+            if keeping.is_some_and(|keeping| level <= keeping) {
+                keeping = None;
+            }
+            if removing.is_none() && matches(&remove, text) {
+                removing = Some(level);
+            } else if keeping.is_none() && matches(&keep, text) {
+                keeping = Some(level);
+            }
+        }
+        if removing.is_none() && (keep.is_empty() || keeping.is_some()) {
+            lines.push(line);
+        }
+    }
+
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+/// Find the `patterns` that don’t match any heading in `docs`.
+///
+/// Patterns may contain `*` and `?` wildcards, as in [`select_sections()`].
+#[must_use]
+pub fn missing_sections<'a>(
+    docs: &str,
+    patterns: &'a [String],
+) -> Vec<&'a str> {
+    let mut fences = Fences::default();
+    let headings: Vec<&str> = docs
+        .split('\n')
+        .filter(|line| fences.classify(line) == LineKind::Text)
+        .filter_map(|line| Some(strip_closing(parse_heading(line)?.1)))
+        .collect();
+    patterns
+        .iter()
+        .map(String::as_str)
+        .filter(|pattern| {
+            !headings
+                .iter()
+                .any(|heading| wildcard_match(pattern, heading))
+        })
+        .collect()
+}
+
+/// Keep only the release sections of a changelog, up to `count` of them.
+///
+/// Release sections are those whose headings contain a version number like
+/// `1.2.0`, e.g. `## [1.2.0] - 2026-01-29` or `## Release 1.2.0`, at the level
+/// of the first such heading. Everything else, such as the title and an
+/// “Unreleased” section, is dropped, except for reference definitions, which
+/// are moved to the end so that links in the kept sections still work.
+#[must_use]
+pub fn changelog_releases(docs: &str, count: Option<usize>) -> String {
+    let mut fences = Fences::default();
+    let mut release_level = None;
+    let mut releases = 0_usize;
+    let mut keeping = false;
+    let mut lines = Vec::new();
+    let mut definitions = Vec::new();
+    for line in docs.split('\n') {
+        if fences.classify(line) == LineKind::Text {
+            if let Some((level, text)) = parse_heading(line) {
+                if release_level.is_none_or(|release| level == release)
+                    && has_version(text)
+                {
+                    release_level = Some(level);
+                    releases = releases.saturating_add(1);
+                    keeping = count.is_none_or(|count| releases <= count);
+                } else if release_level.is_none_or(|release| level <= release) {
+                    keeping = false;
+                }
+            } else if !keeping && split_definition(line).is_some() {
+                definitions.push(line);
+            }
+        }
+        if keeping {
+            lines.push(line);
+        }
+    }
+
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+    if !lines.is_empty() && !definitions.is_empty() {
+        lines.push("");
+        lines.append(&mut definitions);
+    }
+    lines.join("\n")
+}
+
+/// Does `text` contain a version number, i.e. at least two numbers separated
+/// by `.`?
+fn has_version(text: &str) -> bool {
+    text.split(|c: char| !c.is_ascii_digit() && c != '.')
+        .any(|word| {
+            let mut numbers = word.split('.');
+            numbers.clone().count() >= 2
+                && numbers.all(|number| !number.is_empty())
+        })
+}
+
+/// Does `text` match `pattern`, where `*` matches any text and `?` matches
+/// any single character?
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let mut chars = pattern.chars();
+    match chars.next() {
+        None => text.is_empty(),
+        Some('*') => {
+            let rest = chars.as_str();
+            text.char_indices()
+                .map(|(index, _)| index)
+                .chain([text.len()])
+                .any(|index| {
+                    text.get(index..)
+                        .is_some_and(|tail| wildcard_match(rest, tail))
+                })
+        }
+        Some(c) => {
+            let mut text_chars = text.chars();
+            text_chars.next().is_some_and(|t| c == '?' || c == t)
+                && wildcard_match(chars.as_str(), text_chars.as_str())
+        }
+    }
+}
+
+/// Get the first paragraph of `docs`, joined into one line.
+///
+/// Like `rustdoc`’s summary line, this skips headings, code blocks, and HTML
+/// comments before the paragraph. Returns `None` if there is no paragraph.
+#[must_use]
+pub fn first_paragraph(docs: &str) -> Option<String> {
+    let mut fences = Fences::default();
+    let mut paragraph: Vec<&str> = Vec::new();
+    for line in docs.split('\n') {
+        let is_text = fences.classify(line) == LineKind::Text;
+        let line = line.trim();
+        if is_text
+            && !line.is_empty()
+            && parse_heading(line).is_none()
+            && !(line.starts_with("<!--") && line.ends_with("-->"))
+        {
+            paragraph.push(line);
+        } else if !paragraph.is_empty() {
+            break;
+        }
+    }
+    (!paragraph.is_empty()).then(|| paragraph.join(" "))
+}
+
+/// Set the title of `docs`.
+///
+/// If `docs` start with a heading, its text is replaced with `title`.
+/// Otherwise, a level 1 heading is added.
+#[must_use]
+pub fn set_title(docs: &str, title: &str) -> String {
+    let mut lines: Vec<&str> = docs.split('\n').collect();
+    let first = lines.iter().position(|line| !line.trim().is_empty());
+    if let Some(index) = first
+        && let Some((level, _)) = parse_heading(lines[index])
+    {
+        let heading = format!("{} {title}", "#".repeat(level));
+        lines[index] = &heading;
+        lines.join("\n")
+    } else {
+        format!("# {title}\n\n{docs}")
+    }
+}
+
+/// Put `docs` in a collapsed `<details>` element.
+///
+/// If `docs` start with a heading, its text becomes the `<summary>`, which is
+/// always shown. Otherwise, the summary is “Details”.
+#[must_use]
+pub fn collapse(docs: &str) -> String {
+    let trimmed = docs.trim_start_matches(['\n', ' ']);
+    let first = trimmed.split('\n').next().unwrap_or_default();
+    let (summary, body) = match parse_heading(first) {
+        Some((_, text)) => {
+            (text, trimmed.get(first.len()..).unwrap_or_default())
+        }
+        None => ("Details", docs),
+    };
+    format!(
+        "<details>\n<summary>{summary}</summary>\n\n{}\n\n</details>",
+        body.trim_matches('\n')
+    )
+}
+
+/// Make the text of the first heading in `docs` a link to `target`.
+///
+/// Headings that already contain a link are left alone, since links can’t be
+/// nested.
+#[must_use]
+pub fn link_first_heading(docs: &str, target: &str) -> String {
+    let mut linked = false;
+    map_lines(docs, |line, kind| {
+        if !linked
+            && kind == LineKind::Text
+            && let Some((level, text)) = parse_heading(line)
+            && !text.is_empty()
+        {
+            linked = true;
+            if !text.contains("](") {
+                let indent =
+                    line.strip_suffix(line.trim_start()).unwrap_or_default();
+                let hashes = "#".repeat(level);
+                return format!("{indent}{hashes} [{text}]({target})");
+            }
+        }
+        line.to_owned()
+    })
+}
+
+/// Get the lines of `docs` between a `start` marker line and an `end` marker
+/// line, not including the markers.
+///
+/// Markers are matched against whole lines, ignoring surrounding whitespace.
+/// Lines inside code blocks are never treated as markers.
+///
+/// # Errors
+///
+/// Returns an error if either marker couldn’t be found.
+pub fn between(docs: &str, start: &str, end: &str) -> Result<String, String> {
+    let mut fences = Fences::default();
+    let mut lines = docs
+        .split('\n')
+        .map(|line| (line, fences.classify(line) == LineKind::Text));
+    let is_marker = |marker, (line, is_text): (&str, bool)| {
+        is_text && line.trim() == marker
+    };
+
+    lines
+        .find(|&line| is_marker(start, line))
+        .ok_or_else(|| format!("Could not find start marker {start:?}"))?;
+    let mut region = Vec::new();
+    for line in lines {
+        if is_marker(end, line) {
+            return Ok(region.join("\n"));
+        }
+        region.push(line.0);
+    }
+    Err(format!("Could not find end marker {end:?}"))
+}
+
+/// Collapse runs of blank lines outside of code blocks into a single blank
+/// line, and remove blank lines at the start and end.
+#[must_use]
+pub fn collapse_blank_lines(docs: &str) -> String {
+    let mut fences = Fences::default();
+    let mut lines = Vec::new();
+    let mut blank = false;
+    for line in docs.split('\n') {
+        if fences.classify(line) == LineKind::Text && line.trim().is_empty() {
+            blank = !lines.is_empty();
+            continue;
+        }
+        if blank {
+            lines.push("");
+            blank = false;
+        }
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+/// Escape `<`, `>`, and `&` as HTML entities outside of code.
+///
+/// Code blocks and inline code spans are left alone, since Markdown already
+/// displays their contents literally.
+#[must_use]
+pub fn escape_html(docs: &str) -> String {
+    map_lines(docs, |line, kind| match kind {
+        LineKind::Text => map_outside_code_spans(line, |text| {
+            text.replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;")
+        }),
+        _ => line.to_owned(),
+    })
+}
+
+/// Turn code blocks in any of `languages` into `<pre>` HTML blocks.
+///
+/// The `<pre>` element gets the language as its class, e.g.
+/// `<pre class="mermaid">`, which is what JavaScript diagram renderers like
+/// Mermaid look for. The code is HTML-escaped.
+#[must_use]
+pub fn pre_blocks(docs: &str, languages: &[String]) -> String {
+    if languages.is_empty() {
+        return docs.to_owned();
+    }
+    let mut in_pre = false;
+    map_lines(docs, |line, kind| match kind {
+        LineKind::FenceOpen { .. } => {
+            let language = parse_fence(line)
+                .and_then(|(_, _, info)| info.split([',', ' ', '\t']).next())
+                .filter(|language| languages.iter().any(|l| l == language));
+            in_pre = language.is_some();
+            match language {
+                Some(language) => {
+                    format!("{}<pre class=\"{language}\">", indent(line))
+                }
+                None => line.to_owned(),
+            }
+        }
+        LineKind::Code { .. } if in_pre => line
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;"),
+        LineKind::FenceClose if in_pre => {
+            in_pre = false;
+            format!("{}</pre>", indent(line))
+        }
+        _ => line.to_owned(),
+    })
+}
+
+/// Get the leading whitespace of `line`.
+fn indent(line: &str) -> &str {
+    line.strip_suffix(line.trim_start()).unwrap_or_default()
+}
+
+/// Turn inline code naming one of `names`, e.g. `` `Apple` ``, into an
+/// intra-doc link like ``[`Apple`]``.
+///
+/// Code that is already within a link’s brackets is left alone, as is code
+/// in code blocks and code delimited by more than one backtick.
+#[must_use]
+pub fn link_code_spans(docs: &str, names: &[String]) -> String {
+    map_lines(docs, |line, kind| match kind {
+        LineKind::Text => link_line_code_spans(line, names),
+        _ => line.to_owned(),
+    })
+}
+
+/// Turn inline code in `line` naming one of `names` into an intra-doc link.
+fn link_line_code_spans(line: &str, names: &[String]) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut brackets = 0_usize;
+    let mut rest = line;
+    while let Some(start) = rest.find('`') {
+        let (text, from_ticks) = rest.split_at(start);
+        for c in text.chars() {
+            match c {
+                '[' => brackets = brackets.saturating_add(1),
+                ']' => brackets = brackets.saturating_sub(1),
+                _ => {}
+            }
+        }
+        result.push_str(text);
+        let after_ticks = from_ticks.trim_start_matches('`');
+        let ticks = from_ticks.strip_suffix(after_ticks).unwrap_or("");
+        let Some(end) = find_closing_ticks(after_ticks, ticks.len()) else {
+            result.push_str(ticks);
+            rest = after_ticks;
+            continue;
+        };
+        let (code, after) = after_ticks.split_at(end);
+        let span_len = code.len().saturating_add(ticks.len().saturating_mul(2));
+        let span = &from_ticks[..span_len];
+        rest = &after[ticks.len()..];
+        if ticks.len() == 1
+            && brackets == 0
+            && !rest.starts_with(']')
+            && names.iter().any(|name| name == code)
+        {
+            result.push('[');
+            result.push_str(span);
+            result.push(']');
+        } else {
+            result.push_str(span);
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Apply `f` to the parts of `line` that are outside inline code spans.
+///
+/// Code spans are delimited by matching runs of backticks. An unmatched run of
+/// backticks is treated as text.
+pub fn map_outside_code_spans<F>(line: &str, mut f: F) -> String
+where
+    F: FnMut(&str) -> String,
+{
+    let mut result = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(start) = rest.find('`') {
+        let (text, from_ticks) = rest.split_at(start);
+        let after_ticks = from_ticks.trim_start_matches('`');
+        let ticks = from_ticks.strip_suffix(after_ticks).unwrap_or("");
+        let Some(end) = find_closing_ticks(after_ticks, ticks.len()) else {
+            // No closing backticks, so these are literal backticks.
+            result.push_str(&f(text));
+            result.push_str(ticks);
+            rest = after_ticks;
+            continue;
+        };
+        let (code, after) = after_ticks.split_at(end);
+        result.push_str(&f(text));
+        result.push_str(ticks);
+        result.push_str(code);
+        result.push_str(ticks);
+        rest = &after[ticks.len()..];
+    }
+    result.push_str(&f(rest));
+    result
+}
+
+/// Find a run of exactly `count` backticks in `text`.
+pub(crate) fn find_closing_ticks(text: &str, count: usize) -> Option<usize> {
+    let mut run_start = None;
+    for (index, c) in text.char_indices().chain([(text.len(), '\0')]) {
+        match (c == '`', run_start) {
+            (true, None) => run_start = Some(index),
+            (false, Some(start)) => {
+                if index.checked_sub(start) == Some(count) {
+                    return Some(start);
+                }
+                run_start = None;
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Format Rust source as a code block, leaving out its leading inner doc
+/// comments.
+#[must_use]
+pub fn example_code(source: &str) -> String {
+    let code = source
+        .lines()
+        .skip_while(|line| {
+            let line = line.trim_start();
+            line.is_empty() || line.starts_with("//!")
+        })
+        .fold(String::new(), |mut code, line| {
+            code.push_str(line);
+            code.push('\n');
+            code
+        });
+    rust_code_block(&code)
+}
+
+/// Format Rust code as a code block.
+///
+/// The fence is longer than any run of backticks in the code, so that the
+/// code can’t end the code block early.
+#[must_use]
+pub fn rust_code_block(code: &str) -> String {
+    let newline = if code.ends_with('\n') { "" } else { "\n" };
+    let longest = code
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+    let fence = "`".repeat(longest.saturating_add(1).max(3));
+    format!("{fence}rust\n{code}{newline}{fence}")
+}
+
+/// Replace path prefixes within Rust code blocks.
+///
+/// Each `(from, to)` pair replaces `from` with `to` wherever `from` appears as
+/// a whole path prefix, e.g. `("crate::child", "crate")` changes
+/// `crate::child::Foo` to `crate::Foo`, but leaves `crate::children` alone.
+#[must_use]
+pub fn rewrite_doctests(docs: &str, rewrites: &[(String, String)]) -> String {
+    map_lines(docs, |line, kind| match kind {
+        LineKind::Code { is_rust: true } => {
+            rewrites.iter().fold(line.to_owned(), |line, (from, to)| {
+                replace_path(&line, from, to)
+            })
+        }
+        _ => line.to_owned(),
+    })
+}
+
+/// Replace `from` with `to` wherever `from` is a whole path prefix.
+fn replace_path(line: &str, from: &str, to: &str) -> String {
+    /// Can `c` be part of a path?
+    fn is_path_char(c: char) -> bool {
+        c.is_alphanumeric() || c == '_' || c == ':'
+    }
+
+    if from.is_empty() {
+        return line.to_owned();
+    }
+
+    let mut result = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(index) = rest.find(from) {
+        let (before, found) = rest.split_at(index);
+        let after = &found[from.len()..];
+        result.push_str(before);
+        if !before.ends_with(is_path_char)
+            && !after.starts_with(|c: char| c.is_alphanumeric() || c == '_')
+        {
+            result.push_str(to);
+            rest = after;
+        } else {
+            // Not a whole path; skip the first character of the match.
+            let mut chars = found.chars();
+            result.extend(chars.next());
+            rest = chars.as_str();
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Replace the URLs of links and images outside of code.
+///
+/// `f` is called with the URL of each inline link or image, e.g. `![](a.png)`,
+/// and each reference definition, e.g. `[a]: a.png`. It returns the new URL,
+/// or `None` to leave the URL alone.
+pub fn rewrite_urls<F>(docs: &str, mut f: F) -> String
+where
+    F: FnMut(&str) -> Option<String>,
+{
+    map_lines(docs, |line, kind| match (kind, split_definition(line)) {
+        (LineKind::Text, Some((start, url, end))) => {
+            format!("{start}{}{end}", f(url).as_deref().unwrap_or(url))
+        }
+        (LineKind::Text, None) => map_outside_code_spans(line, |text| {
+            rewrite_inline_urls(text, &mut f)
+        }),
+        _ => line.to_owned(),
+    })
+}
+
+/// Split a reference definition like `[a]: url "title"` around its URL.
+fn split_definition(line: &str) -> Option<(&str, &str, &str)> {
+    let (_, after) = line.trim_start().strip_prefix('[')?.split_once("]:")?;
+    let rest = after.trim_start();
+    let url = rest
+        .split([' ', '\t'])
+        .next()
+        .filter(|url| !url.is_empty())?;
+    let start = line.strip_suffix(rest)?;
+    Some((start, url, &rest[url.len()..]))
+}
+
+/// Replace the URLs of inline links and images in `text`.
+fn rewrite_inline_urls<F>(text: &str, f: &mut F) -> String
+where
+    F: FnMut(&str) -> Option<String>,
+{
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some((before, after)) = rest.split_once("](") {
+        let end = after.find([' ', '\t', ')']).unwrap_or(after.len());
+        let (url, after_url) = after.split_at(end);
+        result.push_str(before);
+        result.push_str("](");
+        result.push_str(f(url).as_deref().unwrap_or(url));
+        rest = after_url;
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::assert;
+
+    #[test]
+    fn classify_fences() {
+        let mut fences = Fences::default();
+        let kinds: Vec<_> = [
+            " Text",
+            " ```",
+            " let a = 1;",
+            " ```",
+            " ~~~~text",
+            " ```",
+            " ~~~~",
+            " More text",
+        ]
+        .into_iter()
+        .map(|line| fences.classify(line))
+        .collect();
+        assert!(
+            kinds
+                == [
+                    LineKind::Text,
+                    LineKind::FenceOpen { is_rust: true },
+                    LineKind::Code { is_rust: true },
+                    LineKind::FenceClose,
+                    LineKind::FenceOpen { is_rust: false },
+                    LineKind::Code { is_rust: false },
+                    LineKind::FenceClose,
+                    LineKind::Text,
+                ]
+        );
+    }
+
+    #[test]
+    fn rust_info() {
+        assert!(is_rust_info(""));
+        assert!(is_rust_info("rust"));
+        assert!(is_rust_info("no_run"));
+        assert!(is_rust_info("rust,ignore"));
+        assert!(is_rust_info("ignore-windows"));
+        assert!(is_rust_info("edition2021"));
+        assert!(is_rust_info("compile_fail,E0308"));
+        assert!(!is_rust_info("text"));
+        assert!(!is_rust_info("Markdown"));
+        assert!(!is_rust_info("sh"));
+    }
+
+    #[test]
+    fn example_code_strips_docs() {
+        assert!(
+            example_code("//! # Example\n//!\n//! Docs.\n\nfn main() {}\n")
+                == "```rust\nfn main() {}\n```"
+        );
+    }
+
+    #[test]
+    fn example_code_long_fence() {
+        assert!(
+            example_code("/// ```\n/// a\n/// ```\nfn a() {}")
+                == "````rust\n/// ```\n/// a\n/// ```\nfn a() {}\n````"
+        );
+    }
+
+    #[test]
+    fn doctest_attribute() {
+        assert!(
+            set_doctest_attribute(
+                " ```\n a();\n ```\n ```rust\n b();\n ```\n \
+                ```text\n c\n ```\n ```rust,ignore\n d();\n ```",
+                "ignore",
+            ) == " ```ignore\n a();\n ```\n ```rust,ignore\n b();\n ```\n \
+                ```text\n c\n ```\n ```rust,ignore\n d();\n ```"
+        );
+    }
+
+    #[test]
+    fn doctest_attribute_no_run() {
+        assert!(
+            set_doctest_attribute("~~~~ should_panic\n~~~~", "no_run")
+                == "~~~~should_panic,no_run\n~~~~"
+        );
+    }
+
+    #[test]
+    fn plain_fences() {
+        assert!(
+            label_plain_fences("```\n$ ls\n```\n~~~ rust\n~~~", "text")
+                == "```text\n$ ls\n```\n~~~ rust\n~~~"
+        );
+    }
+
+    #[test]
+    fn dedent_lines() {
+        assert!(
+            dedent(" One\n\n ```\n fn a() {\n     b();\n }\n ```\n   Two")
+                == "One\n\n```\nfn a() {\n    b();\n}\n```\n  Two"
+        );
+        assert!(dedent("One\n Two") == "One\n Two");
+        assert!(dedent(" One\n  \n Two") == "One\n \nTwo");
+    }
+
+    #[test]
+    fn unresolved_links() {
+        assert!(
+            escape_unresolved_links(
+                "[a] [b](url) [c][b] ![d](img) [Def] `[e]` [f\n\
+                ```\n[g]\n```\n[def]: url"
+            ) == "\\[a\\] [b](url) [c][b] ![d](img) [Def] `[e]` [f\n\
+                ```\n[g]\n```\n[def]: url"
+        );
+    }
+
+    #[test]
+    fn headings() {
+        assert!(parse_heading("# Title") == Some((1, "Title")));
+        assert!(parse_heading(" ###   Title  ") == Some((3, "Title")));
+        assert!(parse_heading(" ##") == Some((2, "")));
+        assert!(parse_heading("#Title") == None);
+        assert!(parse_heading("####### Title") == None);
+        assert!(parse_heading("Title") == None);
+    }
+
+    #[test]
+    fn shift_headings_outside_code() {
+        assert!(
+            shift_headings(
+                " # One\n Text\n ```\n # Not a heading\n ```\n ##### Five",
+                2,
+            ) == " ### One\n Text\n ```\n # Not a heading\n ```\n ###### Five"
+        );
+    }
+
+    #[test]
+    fn strip_license_spdx() {
+        assert!(
+            strip_license(
+                " SPDX-License-Identifier: MIT\n\n # Title\n\n Text",
+                &[],
+            ) == " # Title\n\n Text"
+        );
+    }
+
+    #[test]
+    fn strip_license_paragraphs() {
+        assert!(
+            strip_license(
+                "\n Copyright 2026 Someone\n\n Licensed under the Apache \
+                License,\n Version 2.0.\n\n Permission is granted.\n\n Docs",
+                &["Permission is granted".to_owned()],
+            ) == " Docs"
+        );
+    }
+
+    #[test]
+    fn strip_license_no_header() {
+        let docs = " # Copyright handling\n\n Copyright 2026";
+        assert!(strip_license(docs, &[]) == docs);
+        let docs = " ```\n // Copyright\n ```";
+        assert!(strip_license(docs, &[]) == docs);
+    }
+
+    #[test]
+    fn exclude_sections_with_subsections() {
+        assert!(
+            exclude_sections(
+                " # Title\n\n Intro\n\n ## Safety\n\n Unsafe\n\n \
+                ### More\n\n ## Usage\n\n Use it",
+                &["Safety".to_owned()],
+            ) == " # Title\n\n Intro\n\n ## Usage\n\n Use it"
+        );
+    }
+
+    #[test]
+    fn exclude_sections_at_end() {
+        assert!(
+            exclude_sections(
+                " Intro\n\n # Safety\n ```\n # Usage\n ```",
+                &["Safety".to_owned(), "Usage".to_owned()],
+            ) == " Intro"
+        );
+    }
+
+    #[test]
+    fn select_sections_with_wildcards() {
+        let docs = " Intro\n # Usage\n Use.\n ## Usage (deprecated)\n Old.\n \
+            ## Examples\n Ex.\n # Usage notes\n Notes.\n # Safety\n Safe.\n";
+        assert!(
+            select_sections(
+                docs,
+                &["Usage*".to_owned(), "!Usage (deprecated)".to_owned()]
+            ) == " # Usage\n Use.\n ## Examples\n Ex.\n # Usage notes\n Notes."
+        );
+        assert!(
+            select_sections(docs, &["!Us?ge*".to_owned()])
+                == " Intro\n # Safety\n Safe."
+        );
+        assert!(select_sections(docs, &["Nonesuch".to_owned()]) == "");
+    }
+
+    #[test]
+    fn changelog_releases_by_version() {
+        let docs = "# Changelog\n\n## [Unreleased]\n\n* Next.\n\n\
+            ## [1.1.0] - 2026-02-01\n\n### Added\n\n* Pears.\n\n\
+            ## [1.0.0] - 2026-01-01\n\n* Apples.\n\n\
+            [1.1.0]: https://example.com/1.1.0\n\
+            [1.0.0]: https://example.com/1.0.0\n";
+        assert!(
+            changelog_releases(docs, Some(1))
+                == "## [1.1.0] - 2026-02-01\n\n### Added\n\n* Pears.\n\n\
+                [1.1.0]: https://example.com/1.1.0\n\
+                [1.0.0]: https://example.com/1.0.0"
+        );
+        assert!(
+            changelog_releases(docs, None)
+                == "## [1.1.0] - 2026-02-01\n\n### Added\n\n* Pears.\n\n\
+                ## [1.0.0] - 2026-01-01\n\n* Apples.\n\n\
+                [1.1.0]: https://example.com/1.1.0\n\
+                [1.0.0]: https://example.com/1.0.0"
+        );
+        assert!(changelog_releases("# Changelog\n\n* 2026-01-01", None) == "");
+    }
+
+    #[test]
+    fn wildcards() {
+        assert!(wildcard_match("*", ""));
+        assert!(wildcard_match("a*c", "abbc"));
+        assert!(wildcard_match("a?c", "aéc"));
+        assert!(wildcard_match("*é", "café"));
+        assert!(!wildcard_match("a?c", "ac"));
+        assert!(!wildcard_match("a*d", "abc"));
+    }
+
+    #[test]
+    fn set_title_replaces_heading() {
+        assert!(
+            set_title("\n ## Apple processing\n\n Text", "Apples")
+                == "\n## Apples\n\n Text"
+        );
+    }
+
+    #[test]
+    fn set_title_adds_heading() {
+        assert!(set_title(" Text", "Apples") == "# Apples\n\n Text");
+    }
+
+    #[test]
+    fn link_first_heading_only() {
+        assert!(
+            link_first_heading(
+                " Intro\n ```\n # Code\n ```\n ## Apple\n # Other",
+                "mod@apple"
+            ) == " Intro\n ```\n # Code\n ```\n ## [Apple](mod@apple)\n # Other"
+        );
+        assert!(
+            link_first_heading(" # [Apple](x)\n # Other", "y")
+                == " # [Apple](x)\n # Other"
+        );
+        assert!(link_first_heading(" No headings", "y") == " No headings");
+    }
+
+    #[test]
+    fn between_markers() {
+        assert!(
+            between(
+                " Before\n <!-- a -->\n One\n\n Two\n <!-- b -->\n After",
+                "<!-- a -->",
+                "<!-- b -->"
+            ) == Ok(" One\n\n Two".to_owned())
+        );
+    }
+
+    #[test]
+    fn between_ignores_code() {
+        assert!(
+            between(" ```\n a\n ```\n a\n ```\n b\n ```\n b", "a", "b")
+                == Ok(" ```\n b\n ```".to_owned())
+        );
+    }
+
+    #[test]
+    fn first_paragraph_skips_headings() {
+        assert!(
+            first_paragraph(
+                " # Title\n <!-- x -->\n ```\n code\n ```\n \
+                One\n two.\n\n Three."
+            ) == Some("One two.".to_owned())
+        );
+        assert!(
+            first_paragraph(" One\n # Heading\n Two") == Some("One".to_owned())
+        );
+        assert!(first_paragraph(" # Title\n\n ```\n code\n ```") == None);
+    }
+
+    #[test]
+    fn collapse_heading() {
+        assert!(
+            collapse(" ## Apple\n\n Green.\n")
+                == "<details>\n<summary>Apple</summary>\n\n \
+                Green.\n\n</details>"
+        );
+        assert!(
+            collapse(" Green.")
+                == "<details>\n<summary>Details</summary>\n\n \
+                Green.\n\n</details>"
+        );
+    }
+
+    #[test]
+    fn between_missing_marker() {
+        assert!(
+            between(" a\n c", "a", "b")
+                == Err(r#"Could not find end marker "b""#.to_owned())
+        );
+        assert!(
+            between(" b", "a", "b")
+                == Err(r#"Could not find start marker "a""#.to_owned())
+        );
+    }
+
+    #[test]
+    fn pre_blocks_for_languages() {
+        assert!(
+            pre_blocks(
+                " Flow:\n\n ```mermaid\n graph TD; A-->B & C\n ```\n\n\
+                ```text\n<b>\n```",
+                &["mermaid".to_owned()],
+            ) == " Flow:\n\n <pre class=\"mermaid\">\n \
+                graph TD; A--&gt;B &amp; C\n </pre>\n\n```text\n<b>\n```"
+        );
+    }
+
+    #[test]
+    fn suffix_headings_outside_code() {
+        assert!(
+            suffix_headings(
+                " # Apple\n\n ## Examples ##\n\n ```\n # hidden\n ```\n #",
+                " (apple)",
+            ) == " # Apple (apple)\n\n ## Examples (apple)\n\n \
+                ```\n # hidden\n ```\n #"
+        );
+    }
+
+    #[test]
+    fn missing_sections_outside_code() {
+        let docs = " # Apple\n ## Examples ##\n ```\n # Panics\n ```";
+        let patterns =
+            ["Examples".to_owned(), "Pan*".to_owned(), "App?e".to_owned()];
+        assert!(missing_sections(docs, &patterns) == ["Pan*"]);
+    }
+
+    #[test]
+    fn link_code_spans_with_names() {
+        let names = ["Apple".to_owned(), "eat()".to_owned()];
+        assert!(
+            link_code_spans(
+                " `Apple`, `eat()`, `Pear`, [`Apple`], [see `Apple`](url), \
+                ``Apple``\n ```\n `Apple`\n ```",
+                &names,
+            ) == " [`Apple`], [`eat()`], `Pear`, [`Apple`], \
+                [see `Apple`](url), ``Apple``\n ```\n `Apple`\n ```"
+        );
+    }
+
+    #[test]
+    fn collapse_blank_lines_outside_code() {
+        assert!(
+            collapse_blank_lines("\n \n A\n\n \n B\n ```\n\n\n ```\n\n")
+                == " A\n\n B\n ```\n\n\n ```"
+        );
+    }
+
+    #[test]
+    fn escape_html_outside_code() {
+        assert!(
+            escape_html(
+                " Vec<T> & `Vec<T>` ``a`<b>`` `unclosed <\n \
+                ```\n Vec<T>\n ```"
+            ) == " Vec&lt;T&gt; &amp; `Vec<T>` ``a`<b>`` `unclosed &lt;\n \
+                ```\n Vec<T>\n ```"
+        );
+    }
+
+    #[test]
+    fn rewrite_only_rust_code() {
+        let rewrites = [("crate::child".to_owned(), "crate".to_owned())];
+        assert!(
+            rewrite_doctests(
+                " Use crate::child::Foo.\n \
+                ```\n \
+                use crate::child::Foo;\n \
+                ```\n \
+                ```text\n \
+                crate::child::Foo\n \
+                ```",
+                &rewrites,
+            ) == " Use crate::child::Foo.\n \
+                ```\n \
+                use crate::Foo;\n \
+                ```\n \
+                ```text\n \
+                crate::child::Foo\n \
+                ```"
+        );
+    }
+
+    #[test]
+    fn replace_whole_paths() {
+        assert!(
+            replace_path("use crate::child::{A, B};", "crate::child", "crate")
+                == "use crate::{A, B};"
+        );
+        assert!(
+            replace_path("crate::child; crate::child", "crate::child", "x")
+                == "x; x"
+        );
+        assert!(
+            replace_path("use crate::children::A;", "crate::child", "crate")
+                == "use crate::children::A;"
+        );
+        assert!(
+            replace_path("use other::crate::child;", "crate::child", "crate")
+                == "use other::crate::child;"
+        );
+        assert!(
+            replace_path("use mycrate::child::A;", "crate::child", "crate")
+                == "use mycrate::child::A;"
+        );
+    }
+
+    #[test]
+    fn rewrite_link_urls() {
+        let docs = " ![a](a.png) [b](b.md \"B\") `[c](c.md)`\n ```\n \
+            [d](d.md)\n ```\n [e]: e.md \"E\"";
+        let rewritten = rewrite_urls(docs, |url| {
+            (url != "b.md").then(|| format!("../{url}"))
+        });
+        assert!(
+            rewritten
+                == " ![a](../a.png) [b](b.md \"B\") `[c](c.md)`\n ```\n \
+                [d](d.md)\n ```\n [e]: ../e.md \"E\""
+        );
+    }
+}
@@ -0,0 +1,110 @@
+//! Map files to the teams that own them.
+
+use std::path::{Path, PathBuf};
+
+/// Glob patterns that assign files to owners, e.g. teams.
+///
+/// Patterns are relative to a root directory, usually the crate root. `*`
+/// doesn’t match `/`; use `**` to match any number of directories.
+#[derive(Clone, Debug)]
+pub struct Owners {
+    /// The directory that patterns are relative to.
+    root: PathBuf,
+
+    /// Patterns and their owners, in order of precedence.
+    patterns: Vec<(glob::Pattern, String)>,
+}
+
+impl Owners {
+    /// Create owners from `(pattern, owner)` pairs relative to `root`.
+    ///
+    /// The first matching pattern determines a file’s owner.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a pattern is invalid.
+    pub fn new<I>(root: PathBuf, patterns: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let patterns = patterns
+            .into_iter()
+            .map(|(pattern, owner)| {
+                glob::Pattern::new(&pattern)
+                    .map(|pattern| (pattern, owner))
+                    .map_err(|error| {
+                        format!("Invalid glob {pattern:?}: {error}")
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { root, patterns })
+    }
+
+    /// Find the owner of the file at `path`.
+    ///
+    /// Both paths are canonicalized first, so relative paths and symlinks are
+    /// resolved. Returns `None` if no pattern matches, or if `path` isn’t
+    /// within the root directory.
+    #[must_use]
+    pub fn owner(&self, path: &Path) -> Option<&str> {
+        let root = self.root.canonicalize().ok()?;
+        let path = path.canonicalize().ok()?;
+        let relative = path.strip_prefix(root).ok()?;
+        let options = glob::MatchOptions {
+            require_literal_separator: true,
+            ..glob::MatchOptions::new()
+        };
+        self.patterns
+            .iter()
+            .find(|(pattern, _)| pattern.matches_path_with(relative, options))
+            .map(|(_, owner)| owner.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture;
+    use assert2::assert;
+
+    /// Create owners relative to the test fixtures directory.
+    fn owners(patterns: &[(&str, &str)]) -> Owners {
+        Owners::new(
+            fixture(""),
+            patterns.iter().map(|&(pattern, owner)| {
+                (pattern.to_owned(), owner.to_owned())
+            }),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn first_match() {
+        let owners = owners(&[("tree/trunk.rs", "bark"), ("tree/**", "roots")]);
+        assert!(owners.owner(&fixture("tree/trunk.rs")) == Some("bark"));
+        assert!(
+            owners.owner(&fixture("tree/trunk/branch.rs")) == Some("roots")
+        );
+        assert!(owners.owner(&fixture("fruit/apple.rs")) == None);
+    }
+
+    #[test]
+    fn star_within_directory() {
+        let owners = owners(&[("tree/*.rs", "tree")]);
+        assert!(owners.owner(&fixture("tree/mod.rs")) == Some("tree"));
+        assert!(owners.owner(&fixture("tree/trunk/branch.rs")) == None);
+    }
+
+    #[test]
+    fn missing_file() {
+        assert!(
+            owners(&[("**", "all")]).owner(&fixture("nonesuch.rs")) == None
+        );
+    }
+
+    #[test]
+    fn invalid_pattern() {
+        let patterns = [("[".to_owned(), "a".to_owned())];
+        assert!(let Err(_) = Owners::new(PathBuf::new(), patterns));
+    }
+}
@@ -0,0 +1,668 @@
+//! Scan Rust source text for inner doc comments without parsing it.
+//!
+//! `syn` discards comments and whitespace, so anything that depends on the
+//! layout of doc comments in the file has to look at the text directly.
+//! Similarly, `syn` only accepts inner doc comments where Rust does, so finding
+//! them anywhere in a file means only tokenizing it.
+
+use std::borrow::Cow;
+
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use syn::Attribute;
+use syn::parse::Parser;
+
+use crate::DocStyle;
+
+/// Remove a shebang line and `cargo` script frontmatter from `source`.
+///
+/// Single-file scripts can start with a shebang like `#!/usr/bin/env cargo`,
+/// followed by frontmatter: a manifest between lines of `---`. `syn` only
+/// skips the shebang, and the frontmatter may not even be valid Rust tokens,
+/// so both are removed before docs are extracted. An inner attribute like
+/// `#![allow(unused)]` isn’t a shebang.
+pub fn strip_script_header(source: &str) -> &str {
+    let mut rest = source;
+    if let Some(after) = rest.strip_prefix("#!")
+        && !after.trim_start().starts_with('[')
+    {
+        rest = after.split_once('\n').map_or("", |(_, next)| next);
+    }
+
+    let body = rest.trim_start_matches(['\n', '\r', ' ', '\t']);
+    let dashes = body
+        .len()
+        .saturating_sub(body.trim_start_matches('-').len());
+    if dashes < 3 {
+        return rest;
+    }
+    let fence = &body[..dashes];
+    let mut after = body.split_once('\n').map_or("", |(_, next)| next);
+    while !after.is_empty() {
+        let (line, next) = after.split_once('\n').unwrap_or((after, ""));
+        if line.trim_end() == fence {
+            return next;
+        }
+        after = next;
+    }
+    // Unclosed frontmatter is left for the parser to report.
+    rest
+}
+
+/// Find the first contiguous block of inner doc comments in `source`.
+///
+/// Returns the start of `source` up to the end of the block. This is either a
+/// run of `//!` lines without blank lines or other code between them, or a
+/// single `/*! ... */` comment. Blank lines, regular comments, and `#!` lines
+/// before the block are included so that the result can still be parsed.
+///
+/// Returns an empty string if the file doesn’t start with inner doc comments.
+pub fn first_doc_block(source: &str) -> &str {
+    let mut rest = source;
+    let mut end = 0;
+    let mut in_block = false;
+    while !rest.is_empty() {
+        let trimmed = rest.trim_start_matches([' ', '\t']);
+        let (line, next) = rest.split_once('\n').unwrap_or((rest, ""));
+
+        if trimmed.starts_with("/*") {
+            if in_block {
+                break;
+            }
+            let Some(after) = trimmed
+                .find("*/")
+                .and_then(|close| trimmed.get(close..))
+                .and_then(|comment_end| comment_end.strip_prefix("*/"))
+            else {
+                break;
+            };
+            if trimmed.starts_with("/*!") {
+                end = offset(source, after);
+                break;
+            }
+            rest = after;
+        } else if trimmed.starts_with("//!") {
+            in_block = true;
+            rest = next;
+            end = offset(source, rest);
+        } else if in_block {
+            break;
+        } else if line.trim().is_empty()
+            || trimmed.starts_with("//")
+            || trimmed.starts_with("#!")
+        {
+            rest = next;
+        } else {
+            break;
+        }
+    }
+    source.get(..end).unwrap_or_default()
+}
+
+/// Find the inner docs at the start of `source` exactly as written.
+///
+/// Returns the source from the start of the line with the first inner doc
+/// comment or `#![doc ...]` attribute to the end of the last one before the
+/// first item, including `//!` markers and indentation. Regular comments and
+/// other inner attributes between them are kept. Returns an empty string if
+/// the file doesn’t start with inner docs.
+pub fn leading_docs(source: &str) -> &str {
+    let mut rest = source;
+    let mut start = None;
+    let mut end = 0;
+    loop {
+        rest = rest.trim_start();
+        let (is_doc, after) = if let Some(comment) = rest.strip_prefix("//") {
+            if comment.starts_with('/') && !comment.starts_with("//") {
+                break;
+            }
+            let after = comment.find('\n').map_or("", |end| &comment[end..]);
+            (comment.starts_with('!'), after)
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            if comment.starts_with('*')
+                && !comment.starts_with("**")
+                && !comment.starts_with("*/")
+            {
+                break;
+            }
+            (comment.starts_with('!'), skip_block_comment(comment))
+        } else if let Some(attr) = rest.strip_prefix('#')
+            && let Some(group) = attr
+                .trim_start()
+                .strip_prefix('!')
+                .and_then(|bang| bang.trim_start().strip_prefix('['))
+        {
+            (inner_doc_attribute(attr).is_some(), skip_brackets(group))
+        } else {
+            break;
+        };
+        if is_doc {
+            start.get_or_insert_with(|| offset(source, rest));
+            end = offset(source, after);
+        }
+        rest = after;
+    }
+    let Some(start) = start else {
+        return "";
+    };
+    let line_start = source[..start]
+        .rfind('\n')
+        .map_or(0, |newline| newline.saturating_add(1));
+    source.get(line_start..end).unwrap_or_default()
+}
+
+/// Find all inner attributes in `source`, including doc comments.
+///
+/// Unlike parsing the file, this finds inner docs anywhere: inside nested
+/// items, or after items at the top level of a file. The attributes are
+/// returned in source order.
+///
+/// # Errors
+///
+/// Returns an error if `source` couldn’t be tokenized, e.g. because of an
+/// unterminated string.
+pub fn inner_attrs_anywhere(source: &str) -> Result<Vec<Attribute>, String> {
+    let tokens: TokenStream =
+        source.parse().map_err(|error| format!("{error}"))?;
+    let mut attrs = Vec::new();
+    collect_inner_attrs(tokens, &mut attrs);
+    Ok(attrs)
+}
+
+/// Find the inner attributes at the start of `source`, including doc comments.
+///
+/// This only tokenizes `source`, so it works even if the rest of the file uses
+/// syntax that `syn` can’t parse, e.g. unstable syntax.
+///
+/// # Errors
+///
+/// Returns an error if `source` couldn’t be tokenized, e.g. because of an
+/// unterminated string.
+pub fn leading_inner_attrs(source: &str) -> Result<Vec<Attribute>, String> {
+    let tokens: TokenStream =
+        source.parse().map_err(|error| format!("{error}"))?;
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    Ok(tokens
+        .chunks(3)
+        .map_while(inner_attr)
+        .filter_map(|attr| Attribute::parse_inner.parse2(attr).ok())
+        .flatten()
+        .collect())
+}
+
+/// Is the file marked `#![doc(hidden)]`?
+///
+/// Only attributes at the top level of the file count, not those of nested
+/// modules. Returns `false` if `source` couldn’t be tokenized, since that
+/// error is reported when its docs are extracted.
+pub fn is_hidden(source: &str) -> bool {
+    let Ok(tokens) = source.parse::<TokenStream>() else {
+        return false;
+    };
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    let attrs: Vec<Attribute> = tokens
+        .windows(3)
+        .filter_map(inner_attr)
+        .filter_map(|attr| Attribute::parse_inner.parse2(attr).ok())
+        .flatten()
+        .collect();
+    crate::items::is_hidden(&attrs)
+}
+
+/// Turn inner docs in `source` that aren’t in `styles` into something else.
+///
+/// `//!` and `/*!` comments become regular comments, and `#![doc ...]`
+/// attributes become unknown attributes, so that they are ignored when docs
+/// are extracted. Nothing is changed if `styles` is empty.
+///
+/// This is necessary because `rustc` turns doc comments into `doc` attributes
+/// when it tokenizes source, so they can’t be told apart afterward.
+pub fn only_styles<'a>(source: &'a str, styles: &[DocStyle]) -> Cow<'a, str> {
+    if styles.is_empty() {
+        return Cow::Borrowed(source);
+    }
+    let mut result = String::with_capacity(source.len());
+    let mut start = 0;
+    for (marker, style) in inner_doc_markers(source) {
+        if !styles.contains(&style) {
+            let (replacement, len) = match style {
+                DocStyle::Line | DocStyle::Block => (" ", 1),
+                DocStyle::Attribute => ("___", 3),
+            };
+            result.push_str(source.get(start..marker).unwrap_or_default());
+            result.push_str(replacement);
+            start = marker.saturating_add(len);
+        }
+    }
+    result.push_str(source.get(start..).unwrap_or_default());
+    Cow::Owned(result)
+}
+
+/// Find the inner doc comments and `doc` attributes in `source`.
+///
+/// Returns the byte offset of each one’s marker along with its style: the `!`
+/// of `//!` and `/*!` comments, or the `doc` of `#![doc ...]` attributes.
+/// Strings, character literals, and other comments are skipped, so text that
+/// only looks like docs isn’t found.
+fn inner_doc_markers(source: &str) -> Vec<(usize, DocStyle)> {
+    let mut markers = Vec::new();
+    let mut rest = source;
+    while let Some(c) = rest.chars().next() {
+        let after = rest.get(c.len_utf8()..).unwrap_or_default();
+        rest = if let Some(comment) = rest.strip_prefix("//") {
+            if comment.starts_with('!') {
+                markers.push((offset(source, comment), DocStyle::Line));
+            }
+            comment.find('\n').map_or("", |end| &comment[end..])
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            if comment.starts_with('!') {
+                markers.push((offset(source, comment), DocStyle::Block));
+            }
+            skip_block_comment(comment)
+        } else if c == '"' {
+            skip_string(after)
+        } else if c == '\'' {
+            skip_char(after)
+        } else if c == '#' {
+            if let Some(doc) = inner_doc_attribute(after) {
+                markers.push((offset(source, doc), DocStyle::Attribute));
+            }
+            after
+        } else if c.is_alphanumeric() || c == '_' {
+            skip_ident(rest)
+        } else {
+            after
+        };
+    }
+    markers
+}
+
+/// Find the body of the first function named `name` in `source`.
+///
+/// Returns the source between the braces of the body. Functions without a
+/// body, like those declared in traits, are skipped, as are strings and
+/// comments that only look like functions. Brackets in the signature are
+/// tracked, so types like `fn(u8)` and `[u8; 3]` don’t end it.
+pub fn function_body<'a>(source: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = source;
+    let mut in_signature = false;
+    let mut nesting = 0_usize;
+    let mut body_start = None;
+    let mut depth = 0_usize;
+    while let Some(c) = rest.chars().next() {
+        let after = rest.get(c.len_utf8()..).unwrap_or_default();
+        rest = if let Some(comment) = rest.strip_prefix("//") {
+            comment.find('\n').map_or("", |end| &comment[end..])
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            skip_block_comment(comment)
+        } else if c == '"' {
+            skip_string(after)
+        } else if c == '\'' {
+            skip_char(after)
+        } else if c.is_alphanumeric() || c == '_' {
+            let next = skip_ident(rest);
+            if !in_signature && body_start.is_none() && rest.starts_with("fn") {
+                let ident = next.trim_start();
+                let end = ident
+                    .find(|c: char| !c.is_alphanumeric() && c != '_')
+                    .unwrap_or(ident.len());
+                in_signature = offset(rest, next) == 2 && &ident[..end] == name;
+                nesting = 0;
+            }
+            next
+        } else if let Some(arrow) = rest.strip_prefix("->") {
+            arrow
+        } else if in_signature && body_start.is_none() {
+            match c {
+                '(' | '[' | '<' => nesting = nesting.saturating_add(1),
+                ')' | ']' | '>' => nesting = nesting.saturating_sub(1),
+                '{' if nesting == 0 => {
+                    body_start = Some(offset(source, after));
+                    depth = 1;
+                }
+                ';' if nesting == 0 => in_signature = false,
+                _ => {}
+            }
+            after
+        } else if c == '{' && body_start.is_some() {
+            depth = depth.saturating_add(1);
+            after
+        } else if c == '}' && body_start.is_some() {
+            depth = depth.saturating_sub(1);
+            if depth == 0 {
+                return source.get(body_start?..offset(source, rest));
+            }
+            after
+        } else {
+            after
+        };
+    }
+    None
+}
+
+/// Skip an identifier or number, and a string literal if it’s a prefix.
+fn skip_ident(rest: &str) -> &str {
+    let end = rest
+        .find(|c: char| !c.is_alphanumeric() && c != '_')
+        .unwrap_or(rest.len());
+    let (ident, after) = rest.split_at(end);
+    match ident {
+        "r" | "br" | "cr" => skip_raw_string(after),
+        "b" | "c" if after.starts_with('"') => skip_string(&after[1..]),
+        "b" if after.starts_with('\'') => skip_char(&after[1..]),
+        _ => after,
+    }
+}
+
+/// Skip the rest of a string literal after its opening `"`.
+fn skip_string(mut rest: &str) -> &str {
+    while let Some(index) = rest.find(['\\', '"']) {
+        let (_, after) = rest.split_at(index);
+        if let Some(escaped) = after.strip_prefix('\\') {
+            let mut chars = escaped.chars();
+            chars.next();
+            rest = chars.as_str();
+        } else {
+            return &after[1..];
+        }
+    }
+    ""
+}
+
+/// Skip a raw string literal after its `r` prefix, if it is one.
+///
+/// Otherwise, e.g. for a raw identifier like `r#type`, nothing is skipped.
+fn skip_raw_string(rest: &str) -> &str {
+    let after_hashes = rest.trim_start_matches('#');
+    let hashes = &rest[..offset(rest, after_hashes)];
+    let Some(contents) = after_hashes.strip_prefix('"') else {
+        return rest;
+    };
+    let close = format!("\"{hashes}");
+    contents
+        .find(&close)
+        .map_or("", |end| &contents[end.saturating_add(close.len())..])
+}
+
+/// Skip a character literal after its opening `'`.
+///
+/// Lifetimes and labels like `'a` are left alone, since they aren’t closed.
+fn skip_char(rest: &str) -> &str {
+    let mut chars = rest.chars();
+    if chars.next() == Some('\\') {
+        chars.next();
+        let escaped = chars.as_str();
+        return escaped.find('\'').map_or("", |end| &escaped[end..][1..]);
+    }
+    chars.as_str().strip_prefix('\'').unwrap_or(rest)
+}
+
+/// Skip the rest of a possibly nested block comment after its opening `/*`.
+fn skip_block_comment(mut rest: &str) -> &str {
+    let mut depth = 1_usize;
+    while let Some(index) = rest.find(['/', '*']) {
+        let (_, after) = rest.split_at(index);
+        if let Some(after) = after.strip_prefix("/*") {
+            depth = depth.saturating_add(1);
+            rest = after;
+        } else if let Some(after) = after.strip_prefix("*/") {
+            depth = depth.saturating_sub(1);
+            if depth == 0 {
+                return after;
+            }
+            rest = after;
+        } else {
+            rest = &after[1..];
+        }
+    }
+    ""
+}
+
+/// Skip the rest of a group after its opening `[`, including nested groups.
+fn skip_brackets(mut rest: &str) -> &str {
+    let mut depth = 1_usize;
+    while let Some(c) = rest.chars().next() {
+        let after = rest.get(c.len_utf8()..).unwrap_or_default();
+        rest = if c == '"' {
+            skip_string(after)
+        } else if c.is_alphanumeric() || c == '_' {
+            skip_ident(rest)
+        } else if c == '[' {
+            depth = depth.saturating_add(1);
+            after
+        } else if c == ']' {
+            depth = depth.saturating_sub(1);
+            if depth == 0 {
+                return after;
+            }
+            after
+        } else {
+            after
+        };
+    }
+    ""
+}
+
+/// Find the `doc` in an inner `doc` attribute after its opening `#`.
+fn inner_doc_attribute(rest: &str) -> Option<&str> {
+    let rest = rest.trim_start().strip_prefix('!')?;
+    let doc = rest.trim_start().strip_prefix('[')?.trim_start();
+    let after = doc.strip_prefix("doc")?;
+    (!after.starts_with(|c: char| c.is_alphanumeric() || c == '_'))
+        .then_some(doc)
+}
+
+/// Find inner attributes in `tokens` and its groups, recursively.
+fn collect_inner_attrs(tokens: TokenStream, attrs: &mut Vec<Attribute>) {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    for (index, token) in tokens.iter().enumerate() {
+        if let Some(attr) = inner_attr(tokens.get(index..).unwrap_or_default())
+        {
+            if let Ok(parsed) = Attribute::parse_inner.parse2(attr) {
+                attrs.extend(parsed);
+            }
+        } else if let TokenTree::Group(group) = token {
+            collect_inner_attrs(group.stream(), attrs);
+        }
+    }
+}
+
+/// Get the tokens of the inner attribute at the start of `tokens`, if any.
+///
+/// This includes inner doc comments, which the tokenizer turns into
+/// `#![doc = "..."]`.
+fn inner_attr(tokens: &[TokenTree]) -> Option<TokenStream> {
+    if let [
+        TokenTree::Punct(pound),
+        TokenTree::Punct(bang),
+        TokenTree::Group(group),
+        ..,
+    ] = tokens
+        && pound.as_char() == '#'
+        && bang.as_char() == '!'
+        && group.delimiter() == Delimiter::Bracket
+    {
+        Some(tokens.iter().take(3).cloned().collect())
+    } else {
+        None
+    }
+}
+
+/// Get the byte offset of `rest` within `source`.
+///
+/// `rest` must be a suffix of `source`.
+const fn offset(source: &str, rest: &str) -> usize {
+    source.len().saturating_sub(rest.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::assert;
+
+    #[test]
+    fn leading_docs_as_written() {
+        let source = "// Copyright\n\n  //! # Title\n//!\n// Note.\n\
+            #![forbid(unsafe_code)]\n/*! Block\n*/\n\
+            #![doc = \"[x]\"]\n\n/// Outer.\nfn a() {}\n//! Late.\n";
+        assert!(
+            leading_docs(source)
+                == "  //! # Title\n//!\n// Note.\n#![forbid(unsafe_code)]\n\
+                /*! Block\n*/\n#![doc = \"[x]\"]"
+        );
+        assert!(leading_docs("/** Outer. */\n//! Docs") == "");
+        assert!(leading_docs("/**/\n//! Docs\nfn a() {}") == "//! Docs");
+        assert!(leading_docs("fn a() {}") == "");
+    }
+
+    #[test]
+    fn script_header() {
+        assert!(
+            strip_script_header("#!/usr/bin/env cargo\n//! Docs") == "//! Docs"
+        );
+        assert!(
+            strip_script_header(
+                "#!/usr/bin/env -S cargo -Zscript\n\n---cargo\n\
+                [dependencies]\nclap = \"4\" # don't\n---\n//! Docs"
+            ) == "//! Docs"
+        );
+        assert!(strip_script_header("---\n---\n//! Docs") == "//! Docs");
+        assert!(
+            strip_script_header("#![allow(unused)]\n//! Docs")
+                == "#![allow(unused)]\n//! Docs"
+        );
+        assert!(strip_script_header("---\nunclosed") == "---\nunclosed");
+    }
+
+    #[test]
+    fn first_line_block() {
+        assert!(
+            first_doc_block("//! One\n//! Two\n\n//! Three\nfn a() {}")
+                == "//! One\n//! Two\n"
+        );
+    }
+
+    #[test]
+    fn block_after_comments() {
+        assert!(
+            first_doc_block(
+                "#!/usr/bin/env rust\n// Comment\n/* Comment\n */\n\n\
+                //! One\n#![forbid(unsafe_code)]\n//! Two"
+            ) == "#!/usr/bin/env rust\n// Comment\n/* Comment\n */\n\n//! One\n"
+        );
+    }
+
+    #[test]
+    fn block_comment() {
+        assert!(
+            first_doc_block("/*! One\n\nTwo */ /*! Three */")
+                == "/*! One\n\nTwo */"
+        );
+        assert!(first_doc_block("//! One\n/*! Two */") == "//! One\n");
+    }
+
+    #[test]
+    fn leading() {
+        let attrs = leading_inner_attrs(
+            "//! One\n#![doc = \"Two\"]\nfn a() -> u8 { 1 +* }\n//! Three",
+        )
+        .unwrap();
+        assert!(crate::items::docs(&attrs) == " One\nTwo");
+        assert!(let Err(_) = leading_inner_attrs("//! One\nconst S = \""));
+    }
+
+    #[test]
+    fn anywhere() {
+        let attrs = inner_attrs_anywhere(
+            "//! One\nfn a() {\n    //! Two\n}\n\
+            const S: &str = \"//! Not docs\";\n\
+            /*! Three */\n#![doc = \"Four\"]\n#![allow(unused)]",
+        )
+        .unwrap();
+        assert!(crate::items::docs(&attrs) == " One\n Two\n Three \nFour");
+    }
+
+    #[test]
+    fn anywhere_lex_error() {
+        assert!(let Err(_) = inner_attrs_anywhere("\"unterminated"));
+    }
+
+    #[test]
+    fn no_block() {
+        assert!(first_doc_block("fn a() {}\n//! One") == "");
+        assert!(first_doc_block("/* Unterminated") == "");
+        assert!(first_doc_block("//! One") == "//! One");
+    }
+
+    #[test]
+    fn hidden() {
+        assert!(is_hidden("//! Docs\n#![doc(hidden)]\nfn a() {}"));
+        assert!(is_hidden("#![doc(hidden, alias = \"b\")]"));
+        assert!(!is_hidden("//! Docs\nmod a {\n    #![doc(hidden)]\n}"));
+        assert!(!is_hidden("#![doc = \"hidden\"]"));
+        assert!(!is_hidden("#![doc(hidden"));
+    }
+
+    #[test]
+    fn only_line_styles() {
+        let source = "//! Line\n/*! Block */\n#![doc = \"Attribute\"]\n";
+        assert!(
+            only_styles(source, &[DocStyle::Line])
+                == "//! Line\n/*  Block */\n#![___ = \"Attribute\"]\n"
+        );
+        assert!(
+            only_styles(source, &[DocStyle::Block, DocStyle::Attribute])
+                == "//  Line\n/*! Block */\n#![doc = \"Attribute\"]\n"
+        );
+        assert!(only_styles(source, &[]) == source);
+    }
+
+    #[test]
+    fn styles_ignore_strings_and_comments() {
+        let source = "// //! a\n/* /*! b */ */\nconst A: &str = \"//!\";\n\
+            const B: &str = r#\"\"#![doc = c]\"#;\nconst C: char = '\"';\n\
+            const D: [char; 2] = ['\\'', '\\\\'];\n\
+            fn d<'a>(e: &'a str) -> &'a str { e } //! f";
+        assert!(
+            only_styles(source, &[DocStyle::Attribute])
+                == source.replace("} //! f", "} //  f")
+        );
+    }
+
+    #[test]
+    fn function_bodies() {
+        let source = r#"
+trait T { fn a(&self); }
+// fn a() { comment }
+const S: &str = "fn a() { string }";
+fn ab() { wrong }
+fn a<T>(t: T) -> Result<(), ()> where T: Copy {
+    let b = '}';
+    if true { "}" } else { "{" };
+}
+"#;
+        assert!(
+            function_body(source, "a")
+                == Some(
+                    "\n    let b = '}';\n    \
+                    if true { \"}\" } else { \"{\" };\n"
+                )
+        );
+        assert!(function_body(source, "ab") == Some(" wrong "));
+        assert!(function_body(source, "c") == None);
+        assert!(
+            function_body("fn a(f: fn(u8)) { body(); }", "a")
+                == Some(" body(); ")
+        );
+        assert!(
+            function_body("fn a() -> [u8; 3] { [1, 2, 3] }", "a")
+                == Some(" [1, 2, 3] ")
+        );
+        assert!(
+            function_body("fn a(x: [u8; 2]) { x[0]; }", "a") == Some(" x[0]; ")
+        );
+        assert!(
+            function_body("fn a() -> impl Fn() -> u8 { || 1 }", "a")
+                == Some(" || 1 ")
+        );
+    }
+}
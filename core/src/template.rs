@@ -0,0 +1,181 @@
+//! Fill placeholders in a Markdown template with docs.
+//!
+//! Placeholders look like `{{ include "apple.rs" }}`,
+//! `{{ include "apple.rs", shift_headings = 1 }}`, or
+//! `{{ item "apple.rs" Apple }}`. They must be on a single line outside of
+//! code blocks.
+
+use crate::markdown::{Fences, LineKind};
+use proc_macro2::TokenStream;
+use syn::parse::{Parse, ParseStream};
+use syn::{Ident, LitStr, Token};
+
+/// A placeholder in a template.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Placeholder {
+    /// `{{ include "path" }}`: the module docs of a file.
+    Include {
+        /// Path to the file.
+        path: String,
+
+        /// Options for this file after the path, e.g. `shift_headings = 1`,
+        /// not yet parsed. Empty if there are none.
+        options: String,
+    },
+
+    /// `{{ item "path" Name }}`: the docs of an item in a file.
+    Item {
+        /// Path to the file.
+        path: String,
+
+        /// Name of the item, ending with `!` for a macro, or starting with
+        /// `impl` for an `impl` block, e.g. `impl Trait for Type`.
+        name: String,
+    },
+}
+
+impl Parse for Placeholder {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let keyword: Ident = input.parse()?;
+        match keyword.to_string().as_str() {
+            "include" => {
+                let path = input.parse::<LitStr>()?.value();
+                let options = if input.parse::<Option<Token![,]>>()?.is_some() {
+                    input.parse::<TokenStream>()?.to_string()
+                } else {
+                    String::new()
+                };
+                Ok(Self::Include { path, options })
+            }
+            "item" => {
+                let path = input.parse::<LitStr>()?.value();
+                let name = if input.parse::<Option<Token![impl]>>()?.is_some() {
+                    let name = input.parse::<Ident>()?;
+                    if input.parse::<Option<Token![for]>>()?.is_some() {
+                        format!("impl {name} for {}", input.parse::<Ident>()?)
+                    } else {
+                        format!("impl {name}")
+                    }
+                } else {
+                    let mut name = input.parse::<Ident>()?.to_string();
+                    if input.parse::<Option<Token![!]>>()?.is_some() {
+                        name.push('!');
+                    }
+                    name
+                };
+                Ok(Self::Item { path, name })
+            }
+            _ => Err(syn::Error::new(
+                keyword.span(),
+                "expected `include` or `item`",
+            )),
+        }
+    }
+}
+
+/// Replace each placeholder in `template` with the result of `f`.
+///
+/// # Errors
+///
+/// Returns an error if a placeholder is invalid, or if `f` returns an error.
+pub fn render<F>(template: &str, mut f: F) -> Result<String, String>
+where
+    F: FnMut(&Placeholder) -> Result<String, String>,
+{
+    let mut fences = Fences::default();
+    let mut lines = Vec::new();
+    for line in template.split('\n') {
+        if fences.classify(line) == LineKind::Text {
+            lines.push(render_line(line, &mut f)?);
+        } else {
+            lines.push(line.to_owned());
+        }
+    }
+    Ok(lines.join("\n"))
+}
+
+/// Replace the placeholders in a single line.
+///
+/// # Errors
+///
+/// Returns an error if a placeholder is invalid, or if `f` returns an error.
+fn render_line<F>(line: &str, f: &mut F) -> Result<String, String>
+where
+    F: FnMut(&Placeholder) -> Result<String, String>,
+{
+    let mut result = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some((before, after_open)) = rest.split_once("{{")
+        && let Some((inner, after_close)) = after_open.split_once("}}")
+    {
+        let placeholder = syn::parse_str(inner).map_err(|error| {
+            format!("Invalid placeholder {{{{{inner}}}}}: {error}")
+        })?;
+        result.push_str(before);
+        result.push_str(&f(&placeholder)?);
+        rest = after_close;
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::assert;
+
+    /// Render `template`, replacing placeholders with their debug output.
+    fn debug_render(template: &str) -> Result<String, String> {
+        render(template, |placeholder| Ok(format!("<{placeholder:?}>")))
+    }
+
+    #[test]
+    fn placeholders() {
+        assert!(
+            debug_render(
+                "# Title\n{{ include \"a.rs\" }}\nA {{item \"b.rs\" B}} {{"
+            ) == Ok("# Title\n<Include { path: \"a.rs\", options: \"\" }>\n\
+                A <Item { path: \"b.rs\", name: \"B\" }> {{"
+                .to_owned())
+        );
+        assert!(
+            debug_render("{{ include \"a.rs\", trim = true }}")
+                == Ok("<Include { path: \"a.rs\", options: \"trim = true\" }>"
+                    .to_owned())
+        );
+        assert!(
+            debug_render("{{ item \"c.rs\" c! }}")
+                == Ok("<Item { path: \"c.rs\", name: \"c!\" }>".to_owned())
+        );
+        assert!(
+            debug_render("{{ item \"d.rs\" impl A for B }}")
+                == Ok("<Item { path: \"d.rs\", name: \"impl A for B\" }>"
+                    .to_owned())
+        );
+    }
+
+    #[test]
+    fn ignores_code_blocks() {
+        let template = "```\n{{ nonesuch }}\n```";
+        assert!(debug_render(template) == Ok(template.to_owned()));
+    }
+
+    #[test]
+    fn invalid_placeholder() {
+        assert!(
+            debug_render("{{ nonesuch }}")
+                == Err("Invalid placeholder {{ nonesuch }}: \
+                    expected `include` or `item`"
+                    .to_owned())
+        );
+        assert!(let Err(_) = debug_render("{{ include a.rs }}"));
+    }
+
+    #[test]
+    fn error_from_f() {
+        assert!(
+            render("{{ include \"a.rs\" }}", |_| Err("failed".to_owned()))
+                == Err("failed".to_owned())
+        );
+    }
+}
@@ -0,0 +1,263 @@
+//! Render Markdown as text for terminals, e.g. for command line help.
+
+use crate::markdown::{self, Fences, LineKind, parse_heading};
+
+/// How to style rendered text.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Style {
+    /// No styling.
+    #[default]
+    Plain,
+
+    /// ANSI escape codes: bold headings and dim code.
+    Ansi,
+}
+
+impl Style {
+    /// Make `text` bold.
+    fn bold(self, text: &str) -> String {
+        match self {
+            Self::Plain => text.to_owned(),
+            Self::Ansi => format!("\x1b[1m{text}\x1b[22m"),
+        }
+    }
+
+    /// Make `text` dim.
+    fn dim(self, text: &str) -> String {
+        match self {
+            Self::Plain => text.to_owned(),
+            Self::Ansi => format!("\x1b[2m{text}\x1b[22m"),
+        }
+    }
+}
+
+/// Render Markdown docs as plain text.
+///
+/// Markup is removed: headings and links are reduced to their text, code spans
+/// and emphasis lose their markers, and link reference definitions and HTML
+/// comments are dropped. Code blocks are indented by four spaces, without the
+/// lines that `rustdoc` hides in Rust examples.
+#[must_use]
+pub fn render(docs: &str) -> String {
+    render_styled(docs, Style::Plain)
+}
+
+/// Render Markdown docs as text for a terminal that supports ANSI escape codes.
+///
+/// This is like [`render()`], but headings are bold and code is dim.
+#[must_use]
+pub fn render_ansi(docs: &str) -> String {
+    render_styled(docs, Style::Ansi)
+}
+
+/// Render Markdown docs as text with `style`.
+#[must_use]
+pub fn render_styled(docs: &str, style: Style) -> String {
+    let docs = markdown::dedent(docs);
+    let mut fences = Fences::default();
+    let mut lines: Vec<String> = Vec::new();
+    for line in docs.split('\n') {
+        let line = match fences.classify(line) {
+            LineKind::FenceOpen { .. } | LineKind::FenceClose => continue,
+            LineKind::Code { is_rust } => {
+                let trimmed = line.trim_start();
+                if is_rust && (trimmed == "#" || trimmed.starts_with("# ")) {
+                    continue;
+                } else if line.is_empty() {
+                    String::new()
+                } else {
+                    format!("    {}", style.dim(line))
+                }
+            }
+            LineKind::Text => match text_line(line, style) {
+                Some(line) => line,
+                None => continue,
+            },
+        };
+        if !(line.trim().is_empty()
+            && lines.last().is_none_or(|last| last.trim().is_empty()))
+        {
+            lines.push(line);
+        }
+    }
+    while lines.last().is_some_and(|last| last.trim().is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+/// Render a line of Markdown text, or `None` if it should be dropped.
+fn text_line(line: &str, style: Style) -> Option<String> {
+    let trimmed = line.trim();
+    if is_reference_definition(trimmed)
+        || (trimmed.starts_with("<!--") && trimmed.ends_with("-->"))
+    {
+        return None;
+    }
+    if let Some((_, text)) = parse_heading(line) {
+        return Some(style.bold(&inline(text, Style::Plain)));
+    }
+    if trimmed.len() >= 3 && trimmed.chars().all(|c| "*-_ ".contains(c)) {
+        // A thematic break.
+        return Some(line.to_owned());
+    }
+
+    let content = line.trim_start();
+    let indent = line.strip_suffix(content).unwrap_or_default();
+    let (marker, content) = ["* ", "- ", "+ "]
+        .into_iter()
+        .find_map(|marker| Some((marker, content.strip_prefix(marker)?)))
+        .unwrap_or(("", content));
+    Some(format!("{indent}{marker}{}", inline(content, style)))
+}
+
+/// Is `line` a link reference definition like `[label]: url`?
+fn is_reference_definition(line: &str) -> bool {
+    line.strip_prefix('[')
+        .and_then(|rest| rest.split_once("]:"))
+        .is_some_and(|(label, _)| !label.is_empty())
+}
+
+/// Remove inline markup from `text`, styling code spans with `style`.
+fn inline(text: &str, style: Style) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let after = &rest[c.len_utf8()..];
+        match c {
+            '`' => {
+                let after_ticks = rest.trim_start_matches('`');
+                let ticks = rest.len().saturating_sub(after_ticks.len());
+                if let Some(end) =
+                    markdown::find_closing_ticks(after_ticks, ticks)
+                {
+                    result.push_str(&style.dim(after_ticks[..end].trim()));
+                    rest = &after_ticks[end..][ticks..];
+                } else {
+                    result.push_str(&rest[..ticks]);
+                    rest = after_ticks;
+                }
+            }
+            '\\' if after.starts_with(|c: char| c.is_ascii_punctuation()) => {
+                result.push_str(&after[..1]);
+                rest = &after[1..];
+            }
+            '!' if after.starts_with('[') => rest = after,
+            '[' => {
+                if let Some((label, after_label)) = after.split_once(']') {
+                    result.push_str(&inline(label, style));
+                    rest = skip_link_target(after_label);
+                } else {
+                    result.push(c);
+                    rest = after;
+                }
+            }
+            '<' if after.starts_with("!--") => {
+                rest = after.split_once("-->").map_or("", |(_, after)| after);
+            }
+            '<' if after.starts_with("http") && after.contains('>') => {
+                let (url, after) = after.split_once('>').unwrap_or((after, ""));
+                result.push_str(url);
+                rest = after;
+            }
+            '*' | '_' => {
+                let after_run = rest.trim_start_matches(c);
+                let run = &rest[..rest.len().saturating_sub(after_run.len())];
+                let before = result.chars().next_back();
+                let next = after_run.chars().next();
+                let is_word =
+                    |c: Option<char>| c.is_some_and(char::is_alphanumeric);
+                let is_space =
+                    |c: Option<char>| c.is_none_or(char::is_whitespace);
+                let literal = (is_space(before) && is_space(next))
+                    || (c == '_' && is_word(before) && is_word(next));
+                if literal {
+                    result.push_str(run);
+                }
+                rest = after_run;
+            }
+            _ => {
+                result.push(c);
+                rest = after;
+            }
+        }
+    }
+    result
+}
+
+/// Skip the target of a link, e.g. `(url)` or `[reference]`, if present.
+fn skip_link_target(text: &str) -> &str {
+    let close = match text.chars().next() {
+        Some('(') => ')',
+        Some('[') => ']',
+        _ => return text,
+    };
+    text.split_once(close).map_or(text, |(_, after)| after)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::assert;
+
+    #[test]
+    fn headings_and_paragraphs() {
+        assert!(
+            render(" # Serve\n\n Start the *web* server.\n")
+                == "Serve\n\nStart the web server."
+        );
+    }
+
+    #[test]
+    fn inline_markup() {
+        assert!(
+            render(
+                "Use `--port` with **care**, see [`Server`] and \
+                [the guide](https://example.com/guide) or <https://a.b>."
+            ) == "Use --port with care, see Server and the guide or https://a.b."
+        );
+    }
+
+    #[test]
+    fn literal_characters() {
+        assert!(
+            render("snake_case and 2 * 3 and \\[x\\] and `` a`b ``")
+                == "snake_case and 2 * 3 and [x] and a`b"
+        );
+    }
+
+    #[test]
+    fn code_blocks() {
+        assert!(
+            render(
+                "Example:\n\n```\n# use x;\nrun();\n\ndone();\n```\n\n\
+                ```text\n# comment\n```"
+            ) == "Example:\n\n    run();\n\n    done();\n\n    # comment"
+        );
+    }
+
+    #[test]
+    fn ansi() {
+        assert!(
+            render_ansi("# `Serve`\n\nUse `--port`.\n\n```\nrun();\n```")
+                == "\x1b[1mServe\x1b[22m\n\n\
+                Use \x1b[2m--port\x1b[22m.\n\n    \
+                \x1b[2mrun();\x1b[22m"
+        );
+    }
+
+    #[test]
+    fn lists() {
+        assert!(
+            render("* `a`: first\n  - _b_\n1. c") == "* a: first\n  - b\n1. c"
+        );
+    }
+
+    #[test]
+    fn dropped_lines() {
+        assert!(
+            render("<!-- read-doc: skip -->\nSee [x].\n\n[x]: https://x.com\n")
+                == "See x."
+        );
+    }
+}
@@ -0,0 +1,181 @@
+//! Generate a Markdown table of contents linking to the headings in docs.
+
+use crate::markdown::{Fences, LineKind, parse_heading, strip_closing};
+use std::collections::HashMap;
+
+/// IDs that `rustdoc` uses itself on every page.
+///
+/// A heading that would get one of these IDs gets a numbered suffix instead,
+/// e.g. `fields-1`.
+const RESERVED_IDS: &[&str] = &[
+    "aliased-type",
+    "alternative-display",
+    "blanket-implementations",
+    "blanket-implementations-list",
+    "copy-path",
+    "crate-search",
+    "crate-search-div",
+    "default-settings",
+    "deref-methods",
+    "dyn-compatibility",
+    "fields",
+    "foreign-impls",
+    "help",
+    "help-button",
+    "implementations",
+    "implementations-list",
+    "implementors",
+    "implementors-list",
+    "layout",
+    "main-content",
+    "not-displayed",
+    "provided-associated-consts",
+    "provided-associated-types",
+    "provided-methods",
+    "required-associated-consts",
+    "required-associated-types",
+    "required-methods",
+    "rustdoc-modnav",
+    "rustdoc-toc",
+    "search",
+    "settings",
+    "settings-menu",
+    "sidebar-button",
+    "sidebar-vars",
+    "synthetic-implementations",
+    "synthetic-implementations-list",
+    "synthetic-implementors",
+    "synthetic-implementors-list",
+    "toggle-all-docs",
+    "trait-implementations",
+    "trait-implementations-list",
+    "variants",
+];
+
+/// Generate a nested Markdown list linking to every heading in `docs`.
+///
+/// Headings in code blocks are ignored. The shallowest heading level is at
+/// the top level of the list.
+pub fn render(docs: &str) -> String {
+    let mut fences = Fences::default();
+    let headings: Vec<(usize, String)> = docs
+        .split('\n')
+        .filter(|line| fences.classify(line) == LineKind::Text)
+        .filter_map(parse_heading)
+        .map(|(level, text)| (level, strip_links(strip_closing(text))))
+        .filter(|(_, text)| !text.is_empty())
+        .collect();
+    let top = headings.iter().map(|(level, _)| *level).min().unwrap_or(1);
+
+    let mut ids = Ids::default();
+    headings
+        .iter()
+        .map(|(level, text)| {
+            let indent = "  ".repeat(level.saturating_sub(top));
+            format!("{indent}* [{text}](#{})", ids.derive(&slug(text)))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Tracks the heading IDs used so far, like `rustdoc` does.
+struct Ids {
+    /// How many times each ID has been used.
+    used: HashMap<String, usize>,
+}
+
+impl Default for Ids {
+    fn default() -> Self {
+        Self {
+            used: RESERVED_IDS
+                .iter()
+                .map(|id| ((*id).to_owned(), 1))
+                .collect(),
+        }
+    }
+}
+
+impl Ids {
+    /// Get a unique ID based on `id`.
+    ///
+    /// The first use of an ID is unchanged. Later uses get a suffix: `-1`,
+    /// `-2`, and so on.
+    fn derive(&mut self, id: &str) -> String {
+        let count = self.used.entry(id.to_owned()).or_insert(0);
+        let derived = if *count == 0 {
+            id.to_owned()
+        } else {
+            format!("{id}-{count}")
+        };
+        *count = count.saturating_add(1);
+        self.used.entry(derived.clone()).or_insert(1);
+        derived
+    }
+}
+
+/// Convert heading text to an ID the way `rustdoc` does.
+///
+/// Letters, numbers, `-`, and `_` are kept, with ASCII letters lowercased.
+/// ASCII whitespace becomes `-`, and everything else is removed.
+fn slug(text: &str) -> String {
+    text.chars()
+        .filter_map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                Some(c.to_ascii_lowercase())
+            } else if c.is_ascii_whitespace() {
+                Some('-')
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Replace inline links like `[text](url)` with their text.
+fn strip_links(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some((before, after_open)) = rest.split_once('[')
+        && let Some((label, after_label)) = after_open.split_once("](")
+        && let Some((_, after_url)) = after_label.split_once(')')
+    {
+        result.push_str(before);
+        result.push_str(label);
+        rest = after_url;
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::assert;
+
+    #[test]
+    fn nested_list() {
+        assert!(
+            render(
+                "## Apples\n\nText.\n\n### Green apples ###\n\n\
+                ```\n# Not a heading\n```\n\n## Oranges"
+            ) == "* [Apples](#apples)\n  \
+                * [Green apples](#green-apples)\n\
+                * [Oranges](#oranges)"
+        );
+    }
+
+    #[test]
+    fn rustdoc_ids() {
+        assert!(slug("`Vec<T>` & *more*: Ünïcode_1") == "vect--more-Ünïcode_1");
+        assert!(
+            render("# [Apple](apple.rs)\n# Fields\n# Apple\n# Apple")
+                == "* [Apple](#apple)\n* [Fields](#fields-1)\n\
+                * [Apple](#apple-1)\n* [Apple](#apple-2)"
+        );
+    }
+
+    #[test]
+    fn no_headings() {
+        assert!(render("Text.") == "");
+    }
+}
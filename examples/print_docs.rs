@@ -0,0 +1,8 @@
+//! # Print docs read at compile time
+//!
+//! `read_doc::module!` expands to a string literal, so it can be used
+//! anywhere a `&str` is expected, not just in `#![doc]` attributes.
+
+fn main() {
+    println!("{}", read_doc::module!("../tests/fruit/apple.rs"));
+}
@@ -107,21 +107,30 @@ check-changes
 
 echo 'Making sure version is correct.'
 
-awk-in-place Cargo.toml '
-  /^version *=/ && !done {
-    sub(/"[0-9.]+"/, "\"'$version'\"")
-    done=1
-  }
-  { print }'
+# Both crates share a version, and read-doc depends on the same version of
+# read-doc-core.
+for manifest in Cargo.toml core/Cargo.toml ; do
+  awk-in-place "$manifest" '
+    /^version *=/ && !done {
+      sub(/"[0-9.]+"/, "\"'$version'\"")
+      done=1
+    }
+    /^read-doc-core *=/ {
+      sub(/version = "[0-9.]+"/, "version = \"'$version'\"")
+    }
+    { print }'
+done
 
-# Fix docs.rs links in README, if present
-echo 'Updating links in README.md'
-for name in $(crate-names) ; do
-  awk-in-place README.md '{
-      sub(/https:\/\/docs\.rs\/'"$name"'\/[0-9]+.[0-9]+.[0-9]+\//, \
-        "https://docs.rs/'"$name"'/'$version'/")
-      print
-    }'
+# Fix docs.rs links in READMEs, if present
+echo 'Updating links in README.md and core/README.md'
+for readme in README.md core/README.md ; do
+  for name in $(crate-names) ; do
+    awk-in-place "$readme" '{
+        sub(/https:\/\/docs\.rs\/'"$name"'\/[0-9]+.[0-9]+.[0-9]+\//, \
+          "https://docs.rs/'"$name"'/'$version'/")
+        print
+      }'
+  done
 done
 
 cargo check --quiet
@@ -169,7 +178,9 @@ git tag --force --sign --file "$changelog" --cleanup=verbatim "v${version}"
 git push --force --tags origin HEAD
 auto-pr "Release ${version}"
 
-cargo publish
+# read-doc depends on read-doc-core, so it must be published first.
+cargo publish --package read-doc-core
+cargo publish --package read-doc
 
 awk-in-place CHANGELOG.md '
   /^## Release/ && !done {
@@ -1,6 +1,22 @@
 //! # Read module documentation from Rust source files.
 //!
-//! See [`read_doc::module!`](module) for usage.
+//! See [`read_doc::module!`](module) for usage, or
+//! [`#[read_doc::include]`](macro@include) to add docs to any item. To copy
+//! the docs of another item, see [`#[read_doc::doc_from]`](macro@doc_from). To
+//! find files by following `mod` declarations rather than listing them, see
+//! [`read_doc::submodules!`](submodules) and
+//! [`read_doc::recursive!`](recursive). To write the overall narrative in a
+//! Markdown file and fill in docs from source files, see
+//! [`read_doc::template!`](template!). To include a plain Markdown file, see
+//! [`read_doc::markdown!`](markdown!), or to show an example from
+//! `examples/`, see [`read_doc::example!`](example!), or to show the code of
+//! a function, see [`read_doc::function!`](function!). To show recent
+//! releases from a changelog, see [`read_doc::changelog!`](changelog!). For an
+//! overview of every module in a large crate, see [`read_doc::map!`](map!), or
+//! for a table of contents of included docs, see [`read_doc::toc!`](toc!).
+//!
+//! To extract docs the same way at runtime, e.g. in a documentation site
+//! generator, use the [`read-doc-core`](https://docs.rs/read-doc-core) crate.
 //!
 //! If you want to read other doc comments, consider one of the follow crates:
 //!
@@ -8,6 +24,15 @@
 //!   * [doc_for](https://docs.rs/doc_for/latest/doc_for/)
 //!   * [user_doc](https://docs.rs/user_doc/latest/user_doc/)
 //!
+//! # Rebuilding when files change
+//!
+//! Cargo rebuilds a crate when one of its modules changes, but it doesn’t know
+//! about other files read by these macros, such as Markdown files or examples.
+//! On nightly Rust, build with `RUSTFLAGS="--cfg read_doc_unstable"` to tell
+//! the compiler about every file that was read, so that changing any of them
+//! causes a rebuild. On stable Rust, add `cargo::rerun-if-changed` lines to a
+//! build script, or touch a module file after changing them.
+//!
 //! # Minimum supported Rust version
 //!
 //! Currently the minimum supported Rust version (MSRV) is **1.88**. Future
@@ -17,34 +42,26 @@
 #![forbid(unsafe_code)]
 // Enable doc_cfg on docsrs so that we get feature markers.
 #![cfg_attr(docsrs, feature(doc_cfg))]
+// Tell the compiler which files were read. Requires nightly.
+#![cfg_attr(read_doc_unstable, feature(proc_macro_tracked_path))]
 
 use proc_macro::TokenStream;
 use proc_macro2::Span;
 use quote::quote;
-use std::fs;
+use std::borrow::Cow;
 use std::path::{Path, PathBuf};
-use syn::{
-    LitStr, Meta, Token, parse::Parse, parse::ParseStream, parse_macro_input,
-};
+use std::{env, fs};
+use syn::{LitStr, parse_macro_input};
 
-/// Input for `module!` macro.
-struct ModuleInput {
-    /// Paths to the files, relative to the directory of the calling file.
-    paths: Vec<LitStr>,
-}
+mod options;
 
-impl Parse for ModuleInput {
-    fn parse(input: ParseStream) -> syn::Result<Self> {
-        let mut paths = Vec::new();
-        while !input.is_empty() {
-            paths.push(input.parse()?);
-            if !input.is_empty() {
-                input.parse::<Token![,]>()?;
-            }
-        }
-        Ok(Self { paths })
-    }
-}
+use options::{AttributedItem, Base, Deprecated, Input, Options};
+use read_doc_core::directives::{self, Directives};
+use read_doc_core::items::ItemDocs;
+use read_doc_core::{
+    DocExtractor, ExtractError, discover, items, map, markdown, parse_source,
+    read_source, take_read_paths, template, toc,
+};
 
 /// # Read module documentation from Rust source files.
 ///
@@ -61,7 +78,32 @@ impl Parse for ModuleInput {
 ///
 /// Each file’s module documentation will be separated by a blank line.
 ///
-/// Paths are relative to the directory containing the calling file.
+/// If a file’s docs are included from Markdown with
+/// `#![doc = include_str!("path.md")]`, the Markdown file is read instead,
+/// relative to that file. `concat!()` and `env!()` may be used in the path,
+/// e.g. `include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/README.md"))`.
+///
+/// Paths are relative to the directory containing the calling file, unless
+/// they start with `$CRATE/` for the root of the calling crate (the directory
+/// containing its `Cargo.toml`), or `$WORKSPACE/` for the root of its
+/// workspace. Prefixed paths work the same way no matter which file calls the
+/// macro, which is useful for docs shared between files.
+///
+/// A path may also be given as `env!("NAME")` to read it from an environment
+/// variable when the macro is expanded. This is useful with build scripts that
+/// generate files and set `cargo::rustc-env=NAME=path`. Note that, as with the
+/// standard `env!()`, changing the variable doesn’t cause a rebuild by itself.
+///
+/// Paths may contain glob patterns like `*.rs` or `**/*.rs`, in which case
+/// every matching file is included in sorted order. Globs never match the
+/// calling file itself or hidden files, and by default they may only match
+/// files in the crate’s source directories (see `allow_outside` below).
+///
+/// The output only depends on the files, not on the platform or filesystem.
+/// Paths are included in the order they’re passed, and glob matches are sorted
+/// by comparing each component of their paths byte by byte, so `trunk/leaf.rs`
+/// comes before `trunk.rs`. To put files in a different order, list them
+/// individually.
 ///
 /// # Example
 ///
@@ -118,226 +160,1595 @@ impl Parse for ModuleInput {
 /// /// A round fruit.
 /// pub struct Orange;
 /// ```
+///
+/// # Options
+///
+/// Options may be passed as `name = value` after the paths:
+///
+/// ```ignore
+/// #![doc = read_doc::module!(
+///     "apple.rs",
+///     "orange.rs",
+///     rewrite_doctests = { "crate::fruit::apple" => "crate::fruit" },
+/// )]
+/// ```
+///
+/// The same options are accepted by all of the macros in this crate.
+///
+/// Options that control how each file’s docs are read and processed, like
+/// `shift_headings` or `sections`, may also be passed for a single path by
+/// wrapping it in parentheses. These override options passed after the paths.
+/// A list like `sections` passed for a path replaces the list passed after the
+/// paths, rather than adding to it:
+///
+/// ```ignore
+/// #![doc = read_doc::module!(
+///     ("apple.rs", shift_headings = 1),
+///     ("orange.rs", sections = ["Overview"]),
+///     trim = true,
+/// )]
+/// ```
+///
+///   * `allow_outside = true`: allow globs to match files outside of the
+///     crate’s `src/`, `docs/`, `examples/`, and `tests/` directories. By
+///     default that’s an error, since it usually means a glob accidentally
+///     matched build output. (Proc macros can’t emit warnings on stable Rust.)
+///   * `anywhere = true`: read inner doc comments from anywhere in each file,
+///     not just the top. This includes `//!` comments inside inline modules and
+///     function bodies, and `//!` comments after items, which some code
+///     generators produce. The file only has to tokenize, not parse.
+///   * `base = source_dir | manifest_dir | out_dir`: the directory that paths
+///     are relative to. The default is `source_dir`, the directory containing
+///     the calling file. If the calling file’s location is unavailable, which
+///     happens in some IDEs and build setups, `manifest_dir` is used instead.
+///
+///     `manifest_dir` is the root of the crate, where `Cargo.toml` is. Use it
+///     to resolve paths the same way in every environment.
+///
+///     `out_dir` is the [`OUT_DIR`] set by Cargo for crates with a build
+///     script, which is useful for documenting generated code:
+///
+///     ```ignore
+///     #![doc = read_doc::module!(base = out_dir, "generated_api.rs")]
+///     ```
+///
+///     Use a glob like `"*.rs"` to include every generated file.
+///
+///     Cargo rebuilds the crate whenever its build script runs, so the docs
+///     will be updated when generated files change.
+///   * `audience = "name"`: only keep the parts of each file’s docs meant for
+///     this audience, and leave out regions marked for other audiences with
+///     `audience` [directives](#directives).
+///   * `between = ("start", "end")`: only include the lines between a line
+///     containing just `start` and the next line containing just `end`. HTML
+///     comments make good markers, since they won’t show up in the file’s own
+///     documentation:
+///
+///     ```rust
+///     //! Short introduction.
+///     //!
+///     //! <!-- intro-start -->
+///     //! Only this is included.
+///     //! <!-- intro-end -->
+///     //!
+///     //! Lengthy internal details.
+///     ```
+///
+///     It’s an error if either marker is missing from a file.
+///   * `blocks = all | first`: with `first`, only read the first contiguous
+///     block of inner doc comments in each file. A blank line or anything other
+///     than a `//!` comment ends the block. This is useful for files that use
+///     later `//!` comments for internal notes. The default is `all`.
+///   * `collapse_blank_lines = true`: replace runs of blank lines outside of
+///     code blocks with a single blank line, and remove blank lines at the
+///     start and end of each file’s docs. Files are always separated by one
+///     blank line, so this makes combined docs look the same however their
+///     authors spaced their comments.
+///   * `deprecated = "list" | "appendix" | "skip"`: with [`api_summary!`] and
+///     [`methods!`], what to do with items marked `#[deprecated]`. `"appendix"`
+///     moves them to a “Deprecated” section at the end with their deprecation
+///     notes, and `"skip"` leaves them out. Items deprecated `since` a version
+///     later than the calling crate’s are listed as usual. The default,
+///     `"list"`, lists deprecated items with the others.
+///   * `doctests = "test" | "ignore" | "no_run"`: add `ignore` or `no_run` to
+///     Rust code blocks, e.g. <code>\`\`\`rust</code> becomes
+///     <code>\`\`\`rust,ignore</code>. This prevents examples from being tested
+///     twice, or from failing in a context they weren’t written for. The
+///     default, `"test"`, leaves code blocks alone.
+///   * `escape = "none" | "html" | "links" | "all"`: with `"html"`, escape `<`,
+///     `>`, and `&` as HTML entities everywhere except code blocks and inline
+///     code. This is useful if the docs will be embedded in HTML, or if they
+///     contain text like `Vec<T>` that shouldn’t be treated as an HTML tag.
+///     With `"links"`, escape brackets that `rustdoc` would report as broken
+///     intra-doc links, e.g. in `#[derive(Debug)]` or `[note]`, as with
+///     [`markdown!`]. Inline links, reference links with a definition in the
+///     same file, and intra-doc links to code like ``[`Apple`]`` are left
+///     alone, but other shortcut links like `[Apple]` are escaped. `"all"` does
+///     both. The default, `"none"`, leaves the text alone so that intentional
+///     inline HTML and intra-doc links work.
+///   * `exclude_sections = ["Heading", ...]`: remove sections with these
+///     headings, including their subsections. This is useful for sections like
+///     “Implementation details” that only make sense in the original module.
+///   * `format = "markdown" | "text" | "ansi"`: with `"text"`, remove Markdown
+///     markup so the docs can be shown in a terminal, e.g. as the `long_about`
+///     of a [clap] command. Headings and links are reduced to their text, and
+///     code blocks are indented instead of fenced. `"ansi"` is like `"text"`,
+///     but makes headings bold and code dim with ANSI escape codes.
+///   * `heading_suffix = "text"`: add text to the end of every heading, e.g.
+///     `heading_suffix = " ({module})"`. `rustdoc` numbers the links to
+///     headings that appear more than once, e.g. `#examples-1`, so links change
+///     when files are added; this makes each heading unique. The placeholders
+///     are replaced as in `prefix`, and the text isn’t escaped. Ignored with
+///     `format = "text"` or `"ansi"`.
+///   * `hidden = "include" | "skip"`: with `"skip"`, leave out files marked
+///     `#![doc(hidden)]`, so that internal modules matched by a glob or read by
+///     [`recursive!`] don’t end up in the public docs. The default,
+///     `"include"`, treats them like any other file.
+///   * `lenient = true`: if a file can’t be parsed, e.g. because it uses
+///     unstable syntax that isn’t supported yet, read the inner docs at its
+///     start anyway. Without this, a file that can’t be parsed is an error.
+///   * `lines = 1..40`: only include these lines of each file’s docs, counting
+///     from 1. This is useful for including just the start of a module with
+///     long docs. `..=`, like `1..=40`, includes the last line, and either end
+///     may be left off, like `..40` or `5..`. If a file’s docs start on its
+///     first line, these are also its source line numbers. A code block cut off
+///     by the end of the range is closed. Directives outside the range, like
+///     `skip`, still apply.
+///   * `link_headings = true`: with [`submodules!`] and [`recursive!`], make
+///     the first heading of each module’s docs an intra-doc link to the module,
+///     so readers can click through to its full documentation. Links are
+///     relative to the calling module, so the macro should be called from the
+///     module whose submodules it reads.
+///   * `link_to_module = true`: with [`submodules!`] and [`recursive!`], add a
+///     line like <code>See \[\`module\`\](mod@module) for details.</code> after
+///     each module’s docs, linking to the module. With `summary = true`, the
+///     link is added to the end of the summary, so readers of a compact
+///     overview can still click through to each module. As with
+///     `link_headings`, links are relative to the calling module.
+///   * `link_items = true`: turn inline code that names a public item in the
+///     same file, e.g. `` `Apple` `` or `` `eat()` ``, into an intra-doc link.
+///     As with [`api_summary!`], the items must be in scope where the docs are
+///     included, e.g. through `pub use apple::*`.
+///   * `missing = "allow" | "error"`: with `"error"`, fail if a file has no
+///     module docs. This catches mistakes like writing module docs as `///`
+///     comments. The default, `"allow"`, silently skips files without docs.
+///     Files with a `skip` [directive](#directives) are never an error.
+///   * `module = "path"`: read the inner docs of an inline module in each file,
+///     e.g. `module = "fruit::apple"` for `mod fruit { mod apple { ... } }`,
+///     instead of the file’s docs. The path is relative to the file. It’s an
+///     error if the module isn’t found or is declared with `mod name;`.
+///     `anywhere` and `blocks` are ignored, and `lenient` isn’t supported,
+///     since the file must be parsed to find the module.
+///   * `nest_headings = true`: with [`recursive!`], shift the headings of each
+///     module’s docs one level deeper for each level it’s nested, so that the
+///     combined docs read like a book with chapters and sections. Write each
+///     module’s docs as if it were top-level, e.g. starting with `##`.
+///   * `owners = { "pattern" => "owner", ... }`: assign files to owners, such
+///     as teams, with glob patterns relative to the crate root. `*` doesn’t
+///     match `/`, but `**` matches any number of directories. The first
+///     matching pattern wins. It’s an error to include a file without an owner.
+///     Use `{owner}` in `prefix` or `suffix` to mention the owner, e.g. `suffix
+///     = "\n\nMaintained by {owner}."`.
+///   * `pre_blocks = ["mermaid", ...]`: turn code blocks in these languages
+///     into `<pre class="mermaid">` HTML blocks, so that a diagram renderer
+///     loaded with `--html-in-header` can find them. Code blocks in languages
+///     other than Rust are never run as doctests, so this is only needed to
+///     render them. Ignored with `format = "text"` or `"ansi"`.
+///   * `prefix = "text"` and `suffix = "text"`: add text before and after each
+///     file’s docs, e.g. ``prefix = "From `{file}`:\n\n"``. `{file}` is
+///     replaced with the name of the file, `{module}` with the name of its
+///     module (e.g. `fruit` for `fruit/mod.rs`), and `{owner}` with its owner.
+///     The text is added after all other processing, so it isn’t escaped or
+///     shifted.
+///   * `raw = true`: include the inner docs at the start of each file exactly
+///     as written, with their `//!` markers and indentation, e.g. to show them
+///     in a code block in a tutorial. Regular comments and other inner
+///     attributes between the docs are kept too. All processing is skipped, so
+///     other options that change docs, and [directives](#directives), have no
+///     effect.
+///   * `require_sections = ["Examples", ...]`: fail if a file’s docs don’t have
+///     a heading matching each of these patterns, which may use `*` and `?`
+///     wildcards. This enforces a documentation policy, e.g. that every module
+///     has examples. Files without docs are only an error with `missing =
+///     "error"`.
+///   * `require_submodules = true`: with [`module!`] and
+///     [`include`](macro@include), fail if a submodule declared in the calling
+///     file has docs that aren’t included. This catches new submodules that
+///     were never added to the list of paths. Submodules with a `skip`
+///     [directive](#directives) don’t need to be included. (Proc macros can’t
+///     emit warnings on stable Rust.)
+///   * `rewrite_assets = "url"`: rewrite relative URLs of images and links to
+///     files, e.g. `![](diagram.png)`, to `url` followed by the file’s path
+///     relative to the crate root, e.g. `rewrite_assets =
+///     "https://raw.githubusercontent.com/owner/repo/main"`. This keeps them
+///     working when docs are included in another module or shown on docs.rs.
+///     Only URLs of files that exist are rewritten, so intra-doc links and
+///     links to other `rustdoc` pages are left alone.
+///   * `rewrite_doctests = { "from" => "to", ... }`: replace path prefixes in
+///     Rust code blocks, so that examples still compile when their docs are
+///     included in another module. Only whole path prefixes are replaced, so
+///     `"crate::fruit::apple" => "crate::fruit"` changes
+///     `crate::fruit::apple::Apple` to `crate::fruit::Apple`.
+///   * `sections = ["Heading*", "!Heading", ...]`: only keep sections whose
+///     headings match one of these selectors, including their subsections.
+///     Selectors may use `*` to match any text and `?` to match any character.
+///     A selector starting with `!` removes matching sections instead, even
+///     inside kept sections. With only `!` selectors, everything else is kept;
+///     otherwise, text before the first heading is dropped.
+///   * `shift_headings = N`: increase the level of every heading by `N`, e.g.
+///     with `shift_headings = 1` a `# Title` becomes `## Title`. Headings are
+///     never made deeper than level 6.
+///   * `strip_license = true | ["pattern", ...]`: remove license boilerplate
+///     from the start of each file’s docs. Paragraphs are removed while they
+///     have a line starting with an SPDX tag like `SPDX-License-Identifier:`,
+///     “Copyright”, or “Licensed under”. Pass a list of strings instead of
+///     `true` to also remove paragraphs containing any of them.
+///   * `styles = ["line", "block", "attr"]`: only read docs written in these
+///     styles: `//!` comments, `/*! ... */` comments, and `#![doc = "..."]`
+///     attributes respectively. This is useful if some styles are reserved for
+///     other purposes, e.g. `styles = ["line"]` to skip generated `#![doc =
+///     ...]` notes while keeping `//!` prose. All styles are read by default.
+///   * `summary = true`: only include the first paragraph of each file’s docs,
+///     skipping headings and code, as a list item. The items are put on
+///     consecutive lines, so several files make a compact list, e.g. one
+///     sentence about each submodule from [`submodules!`].
+///   * `trim = true`: remove the leading whitespace common to every line of a
+///     file’s docs, such as the space after `//!`. Code blocks keep their
+///     relative indentation.
+///
+/// # Directives
+///
+/// Files can control how their own docs are included with a directive in an
+/// HTML comment, which won’t appear in their own documentation:
+///
+/// ```rust
+/// //! <!-- read-doc: title=Apples, shift_headings=1 -->
+/// //! # Apple processing
+/// ```
+///
+/// Directives apply whenever the file is included by any of the macros in this
+/// crate. Separate multiple directives with commas.
+///
+///   * `skip`: don’t include this file’s docs at all.
+///   * `title=Text`: replace the text of the first heading with `Text`, or add
+///     a level 1 heading if the docs don’t start with one.
+///   * `shift_headings=N`: like the option, but overrides it for this file.
+///   * `collapse`: put this file’s docs in a collapsed `<details>` element. If
+///     the docs start with a heading, its text becomes the `<summary>` shown
+///     while collapsed. Ignored with `format = "text"` or `"ansi"`.
+///   * `audience=name` and `/audience`: mark the lines between them as only
+///     meant for `name`, e.g. `internal`. They’re left out unless the
+///     `audience` option is unset or set to `name`. Each of these directives
+///     must be in its own comment, and regions can’t be nested.
+///
+///     ```rust
+///     //! Eat an apple.
+///     //!
+///     //! <!-- read-doc: audience=internal -->
+///     //! The apple supplier is being replaced.
+///     //! <!-- read-doc: /audience -->
+///     ```
+///
+/// [`OUT_DIR`]: https://doc.rust-lang.org/cargo/reference/environment-variables.html#environment-variables-cargo-sets-for-crates
+/// [clap]: https://docs.rs/clap
 #[proc_macro]
 pub fn module(input: TokenStream) -> TokenStream {
-    fn inner(input: &ModuleInput) -> syn::Result<TokenStream> {
-        let base_dir = get_source_dir()?;
+    let input = parse_macro_input!(input as Input);
+    finish(joined_module_docs(&input).map(|docs| output(&docs)), &input)
+}
 
-        let docs = input
-            .paths
-            .iter()
-            .filter_map(|path_lit| {
-                let path = Path::new(&base_dir).join(path_lit.value());
-                fs::read_to_string(&path)
-                    .map_err(|error| error.to_string())
-                    .and_then(|content| extract_inner_docs(&content))
-                    .map(|docs| if docs.is_empty() { None } else { Some(docs) })
-                    .map_err(|error| {
-                        syn::Error::new(
-                            path_lit.span(),
-                            format!("Failed to read {path:?}: {error}"),
-                        )
-                    })
-                    .transpose()
+/// # Append module documentation from Rust source files to an item’s docs.
+///
+/// ```ignore
+/// /// A basket of fruit.
+/// ///
+/// #[read_doc::include("apple.rs", "orange.rs", shift_headings = 1)]
+/// pub struct Basket;
+/// ```
+///
+/// This attribute reads docs exactly like [`module!`], then adds them to the
+/// item it’s placed on, after any docs the item already has. It works on any
+/// item, including `mod` declarations and `pub use` re-exports.
+///
+/// This is equivalent to `#[doc = read_doc::module!(...)]` placed after the
+/// item’s doc comments, but it doesn’t need to be placed carefully. Pass
+/// `separator = "text"` to add text between the item’s own docs and the
+/// included docs, e.g. `separator = "\n---\n"` for a horizontal rule. Doc
+/// comments are joined by line breaks, so `"\n"` adds a blank line. The
+/// separator is left out if the item has no docs of its own.
+///
+/// ## Conditional docs
+///
+/// ```ignore
+/// #[read_doc::include("common.rs", ("unix.rs", cfg = unix))]
+/// pub mod platform;
+/// ```
+///
+/// Unlike [`module!`], this attribute accepts paths of the form
+/// `("path", cfg = predicate)`. The docs of those files are only included when
+/// the predicate is true for the crate being compiled, just like
+/// `#[cfg_attr(predicate, doc = ...)]`. Every file is still read, so an error
+/// in a gated file is reported even if its docs wouldn’t be included.
+#[proc_macro_attribute]
+pub fn include(args: TokenStream, item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(args as Input);
+    let item = parse_macro_input!(item as AttributedItem);
+    let docs = module_docs(&input).map(|docs| {
+        docs.into_iter()
+            .map(|(index, docs)| {
+                let cfg = index
+                    .and_then(|index| input.paths.get(index))
+                    .and_then(|path| path.cfg.clone());
+                (cfg, docs)
             })
-            .collect::<syn::Result<Vec<String>>>()?
-            .join("\n\n"); // FIXME all errors
+            .collect()
+    });
+    append_docs(docs, &input, item)
+}
 
-        let lit = LitStr::new(&docs, Span::call_site());
-        Ok(quote! { #lit }.into())
+/// # Copy the docs of another item onto this one.
+///
+/// ```ignore
+/// /// A wrapper around `Apple`.
+/// ///
+/// #[read_doc::doc_from("apple.rs", item = "Apple")]
+/// pub struct AppleWrapper(apple::Apple);
+/// ```
+///
+/// This attribute finds the item named by the `item` option at the top level
+/// of the passed file, and adds its outer doc comments to the item it’s placed
+/// on, after any docs the item already has. This keeps the docs of wrappers
+/// and re-exports in sync with the originals.
+///
+/// `macro_rules!` macros can be found even if they’re defined in an inline
+/// module, since `#[macro_export]` makes them available at the crate root. Add
+/// `!` to the name, e.g. `item = "fruit!"`, to only look for a macro.
+///
+/// On a re-export of a single item, like `pub use apple::Apple;`, the `item`
+/// option may be left out to copy the docs of the re-exported item:
+///
+/// ```ignore
+/// #[read_doc::doc_from("apple.rs")]
+/// pub use apple::Apple;
+/// ```
+///
+/// The docs of an `impl` block can be copied with `item = "impl Trait for
+/// Type"`, or `item = "impl Type"` for an inherent `impl`. This is useful for
+/// docs that describe how a type implements a protocol.
+///
+/// As with [`module!`], the path is relative to the directory containing the
+/// calling file. [Options](module#options) may be passed after the path, as
+/// may `separator`, as with [`include`](macro@include).
+#[proc_macro_attribute]
+pub fn doc_from(args: TokenStream, item: TokenStream) -> TokenStream {
+    fn inner(input: &Input, item: &AttributedItem) -> syn::Result<String> {
+        let name = match &input.options.item {
+            Some(name) => name.clone(),
+            None => reexported_name(&item.rest).ok_or_else(|| {
+                syn::Error::new(
+                    Span::call_site(),
+                    r#"expected an `item = "Name"` option"#,
+                )
+            })?,
+        };
+        let file = parse_input_file(input)?;
+        let docs = items::item_docs(&file, &name.value()).ok_or_else(|| {
+            syn::Error::new(
+                name.span(),
+                format!("No item named {:?} found", name.value()),
+            )
+        })?;
+        Ok(input
+            .options
+            .extractor
+            .process_docs(&docs, &Directives::default()))
     }
 
-    match inner(&parse_macro_input!(input as ModuleInput)) {
-        Ok(stream) => stream,
-        Err(error) => error.to_compile_error().into(),
+    let input = parse_macro_input!(args as Input);
+    let item = parse_macro_input!(item as AttributedItem);
+    let docs = inner(&input, &item).map(|docs| vec![(None, docs)]);
+    append_docs(docs, &input, item)
+}
+
+/// Get the name of the single item re-exported by `item`, if it’s a `use`
+/// declaration like `pub use apple::Apple;`.
+///
+/// The original name is returned for a renamed item like `Apple as Fruit`.
+fn reexported_name(item: &proc_macro2::TokenStream) -> Option<LitStr> {
+    let item_use: syn::ItemUse = syn::parse2(item.clone()).ok()?;
+    let mut tree = &item_use.tree;
+    let ident = loop {
+        tree = match tree {
+            syn::UseTree::Path(path) => &path.tree,
+            syn::UseTree::Name(name) => break &name.ident,
+            syn::UseTree::Rename(rename) => break &rename.ident,
+            syn::UseTree::Glob(_) | syn::UseTree::Group(_) => return None,
+        };
+    };
+    Some(LitStr::new(&ident.to_string(), ident.span()))
+}
+
+/// Add `docs` to the end of an item’s doc comments.
+///
+/// Each part of `docs` becomes a separate `doc` attribute, wrapped in
+/// `cfg_attr` if it has a `cfg` predicate. Parts are separated by blank lines.
+/// If the item already has docs, the `separator` option is added before the
+/// first part.
+///
+/// If there was an error, the item is output unchanged along with the error,
+/// so that the error isn’t hidden by errors about the item being missing.
+fn append_docs(
+    docs: syn::Result<Vec<(Option<proc_macro2::TokenStream>, String)>>,
+    input: &Input,
+    item: AttributedItem,
+) -> TokenStream {
+    let AttributedItem { attrs, rest } = item;
+    let separator = if attrs.iter().any(|attr| attr.path().is_ident("doc")) {
+        input.options.separator.as_str()
+    } else {
+        ""
+    };
+    track_read_paths();
+    match docs {
+        Ok(docs) => {
+            let doc_attrs =
+                docs.into_iter().enumerate().map(|(i, (cfg, docs))| {
+                    let docs = if i == 0 {
+                        format!("{separator}{docs}")
+                    } else {
+                        format!("\n{docs}")
+                    };
+                    let lit = LitStr::new(&docs, Span::call_site());
+                    cfg.map_or_else(
+                        || quote! { #[doc = #lit] },
+                        |cfg| quote! { #[cfg_attr(#cfg, doc = #lit)] },
+                    )
+                });
+            quote! { #(#attrs)* #(#doc_attrs)* #rest }.into()
+        }
+        Err(error) => {
+            let error = add_base_dir_note(error, input).to_compile_error();
+            quote! { #error #(#attrs)* #rest }.into()
+        }
     }
 }
 
-/// Extract inner doc comments from Rust source.
+/// Read the docs for [`module!`], separated by blank lines.
 ///
 /// # Errors
 ///
-/// Returns an error if there was a problem parsing the file.
-fn extract_inner_docs(content: &str) -> Result<String, String> {
-    Ok(syn::parse_file(content)
-        .map_err(|error| error.to_string())?
-        .attrs
+/// Returns an error if any path is gated by `cfg`, or if the docs couldn’t be
+/// read.
+fn joined_module_docs(input: &Input) -> syn::Result<String> {
+    input.ungated()?;
+    let docs: Vec<String> = module_docs(input)?
         .into_iter()
-        .filter_map(|attr| {
-            if attr.path().is_ident("doc")
-                && let Meta::NameValue(meta) = &attr.meta
-                && let syn::Expr::Lit(expr_lit) = &meta.value
-                && let syn::Lit::Str(lit_str) = &expr_lit.lit
-            {
-                Some(lit_str.value())
-            } else {
-                // Skip attributes other than a doc attributes with a value.
-                None
+        .map(|(_, docs)| docs)
+        .collect();
+    Ok(docs.join(input.options.file_separator()))
+}
+
+/// Read the docs for [`module!`] and [`include`](macro@include).
+///
+/// Consecutive files that aren’t gated by `cfg` are combined. Each part is
+/// returned with the index of its gated path in `input.paths`, if any.
+///
+/// # Errors
+///
+/// Returns an error if a glob was invalid or matched nothing, or if a file
+/// couldn’t be read or parsed.
+fn module_docs(input: &Input) -> syn::Result<Vec<(Option<usize>, String)>> {
+    let source_file = Span::call_site().local_file();
+
+    let mut files = Vec::new();
+    for (index, arg) in input.paths.iter().enumerate() {
+        let full_path = arg.path.value();
+        let (base_dir, path) =
+            split_path_prefix(&full_path, &input.options.base)?;
+        let span = arg.path.span();
+        let gate = arg.cfg.as_ref().map(|_| index);
+        let own_options = arg.extractor.as_ref().map(|_| index);
+        if discover::is_glob(path) {
+            let matches =
+                discover::glob(&base_dir, path, source_file.as_deref())
+                    .map_err(|error| syn::Error::new(span, error))?;
+            check_glob_matches(&matches, &full_path, span, &input.options)?;
+            files.extend(
+                matches
+                    .into_iter()
+                    .map(|path| (path, span, (gate, own_options))),
+            );
+        } else {
+            files.push((base_dir.join(path), span, (gate, own_options)));
+        }
+    }
+    if input.options.require_submodules {
+        check_submodules(&files, source_file.as_deref(), &input.options)?;
+    }
+
+    // Files are extracted together, except for those with their own options.
+    let mut docs = Vec::new();
+    let mut errors: Option<syn::Error> = None;
+    for group in files.chunk_by(|(_, _, a), (_, _, b)| a.1 == b.1) {
+        let extractor = group
+            .first()
+            .and_then(|(_, _, (_, own_options))| *own_options)
+            .and_then(|index| input.paths.get(index)?.extractor.as_ref())
+            .unwrap_or(&input.options.extractor);
+        let group = group
+            .iter()
+            .map(|(path, span, (gate, _))| (path.clone(), *span, *gate));
+        match collect_docs(group, extractor) {
+            Ok(group_docs) => docs.extend(group_docs),
+            Err(error) => match &mut errors {
+                Some(errors) => errors.combine(error),
+                None => errors = Some(error),
+            },
+        }
+    }
+    if let Some(errors) = errors {
+        return Err(errors);
+    }
+
+    let mut parts: Vec<(Option<usize>, String)> = Vec::new();
+    for (gate, docs) in docs {
+        match parts.last_mut() {
+            Some((last, combined)) if gate.is_none() && last.is_none() => {
+                combined.push_str(input.options.file_separator());
+                combined.push_str(&docs);
             }
+            _ => parts.push((gate, docs)),
+        }
+    }
+    Ok(parts)
+}
+
+/// # Read documentation from all modules in a directory, recursively.
+///
+/// ```ignore
+/// //! # Overall module documentation
+/// #![doc = read_doc::recursive!(".")]
+///
+/// mod submodule1;
+/// mod submodule2;
+/// ```
+///
+/// This macro finds the root module file in the passed directory (`mod.rs`,
+/// `lib.rs`, or `main.rs`, or `name.rs` next to a directory `name/`), then
+/// follows its `mod name;` declarations to find submodules. Both the `name.rs`
+/// and `name/mod.rs` layouts are supported.
+///
+/// The inner doc comments of every submodule are combined in declaration order,
+/// with each module’s docs followed immediately by the docs of its own
+/// submodules. The root module’s own docs are not included, so it’s safe to
+/// use this macro in the root module itself, as above.
+///
+/// As with [`module!`], each module’s documentation will be separated by a
+/// blank line, and the path is relative to the directory containing the
+/// calling file. [Options](module#options) may be passed after the path. To
+/// make the heading levels mirror the module hierarchy, e.g. for an overview
+/// of a whole crate’s architecture, pass `nest_headings = true`.
+#[proc_macro]
+pub fn recursive(input: TokenStream) -> TokenStream {
+    fn inner(input: &Input) -> syn::Result<TokenStream> {
+        let path_lit = input.single_path()?;
+        let dir = resolve_path(path_lit, &input.options.base)?;
+        let error = |error| syn::Error::new(path_lit.span(), error);
+
+        let root = discover::root_module(&dir).map_err(error)?;
+        let modules = discover::module_tree(&root).map_err(error)?;
+        combine_module_docs(modules, path_lit.span(), &input.options)
+            .map(|docs| output(&docs))
+    }
+
+    let input = parse_macro_input!(input as Input);
+    finish(inner(&input), &input)
+}
+
+/// # Generate a map of every module in a directory tree.
+///
+/// ```ignore
+/// //! # My crate
+/// //!
+/// //! ## Module map
+/// //!
+/// #![doc = read_doc::map!(".", shift_headings = 2)]
+/// ```
+///
+/// This macro finds modules the same way as [`recursive!`], then generates a
+/// Markdown list of every module with a one line summary of its docs:
+///
+/// ```Markdown
+/// * [`fruit`]: Fruit functionality.
+///
+/// # `fruit/`
+///
+/// * [`fruit::apple`]: Green or red, we don't care.
+/// * [`fruit::orange`]: Various orange-related code.
+/// ```
+///
+/// Modules are grouped by the directory containing their source file, with a
+/// heading for each directory. The summary is the first line of the module’s
+/// docs that isn’t a heading. Modules with a [`skip`](module#directives)
+/// directive are left out.
+///
+/// Each module is linked with an intra-doc link relative to the root module,
+/// so this is intended to be used in the root module itself.
+///
+/// As with [`module!`], the path is relative to the directory containing the
+/// calling file. [Options](module#options) may be passed after the path.
+#[proc_macro]
+pub fn map(input: TokenStream) -> TokenStream {
+    fn inner(input: &Input) -> syn::Result<TokenStream> {
+        let path_lit = input.single_path()?;
+        let dir = resolve_path(path_lit, &input.options.base)?;
+        let error = |error| syn::Error::new(path_lit.span(), error);
+
+        let root = discover::root_module(&dir).map_err(error)?;
+        let root_dir = root.path.parent().unwrap_or(&dir).to_path_buf();
+        let mut modules = Vec::new();
+        for (path, module) in discover::module_tree(&root).map_err(error)? {
+            let Some(docs) = input
+                .options
+                .extractor
+                .read_docs(&module.path)
+                .map_err(|e| error(e.to_string()))?
+            else {
+                continue;
+            };
+            let (docs, directives) = directives::take(
+                &docs,
+                input.options.extractor.audience.as_deref(),
+                None,
+            )
+            .map_err(|message| {
+                error(
+                    ExtractError::invalid_docs(&module.path, message)
+                        .to_string(),
+                )
+            })?;
+            if directives.skip {
+                continue;
+            }
+            let dir = module
+                .path
+                .parent()
+                .and_then(|parent| parent.strip_prefix(&root_dir).ok())
+                .map(|dir| dir.to_string_lossy().replace('\\', "/"))
+                .unwrap_or_default();
+            modules.push(map::ModuleEntry {
+                path,
+                dir,
+                summary: map::summary(&docs),
+            });
+        }
+
+        Ok(output(&input.options.extractor.process_docs(
+            &map::render(&modules),
+            &Directives::default(),
+        )))
+    }
+
+    let input = parse_macro_input!(input as Input);
+    finish(inner(&input), &input)
+}
+
+/// # Generate a table of contents for the docs of Rust source files.
+///
+/// ```ignore
+/// //! # Fruit
+/// //!
+/// //! ## Contents
+/// //!
+/// #![doc = read_doc::toc!("apple.rs", "orange.rs", shift_headings = 1)]
+/// #![doc = read_doc::module!("apple.rs", "orange.rs", shift_headings = 1)]
+/// ```
+///
+/// This macro reads docs exactly like [`module!`], then generates a nested
+/// Markdown list linking to each of their headings:
+///
+/// ```Markdown
+/// * [Apple processing](#apple-processing)
+///   * [Varieties](#varieties)
+/// * [Orange processing](#orange-processing)
+/// ```
+///
+/// The links use the IDs that `rustdoc` gives headings, including the numbered
+/// suffixes it adds to duplicate headings. IDs only match if the docs are
+/// included on the same page with the same options, and the page has no
+/// other headings with the same text before them.
+#[proc_macro]
+pub fn toc(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as Input);
+    finish(
+        joined_module_docs(&input).map(|docs| output(&toc::render(&docs))),
+        &input,
+    )
+}
+
+/// # Read documentation from the submodules declared in a file.
+///
+/// ```ignore
+/// //! # Overall module documentation
+/// #![doc = read_doc::submodules!("mod.rs")]
+///
+/// mod submodule1;
+/// mod submodule2;
+/// ```
+///
+/// This macro parses the passed Rust source file, finds its `mod name;`
+/// declarations, and combines the inner doc comments of those submodules in
+/// declaration order. Submodules of submodules are not included; see
+/// [`recursive!`] for that.
+///
+/// Submodules are found the same way the compiler finds them: if the file is
+/// `mod.rs`, `lib.rs`, or `main.rs` they are looked for in the same directory,
+/// otherwise they are looked for in a directory named after the file (e.g.
+/// `fruit/` for `fruit.rs`).
+///
+/// As with [`module!`], each module’s documentation will be separated by a
+/// blank line, and the path is relative to the directory containing the
+/// calling file. [Options](module#options) may be passed after the path.
+#[proc_macro]
+pub fn submodules(input: TokenStream) -> TokenStream {
+    fn inner(input: &Input) -> syn::Result<TokenStream> {
+        let path_lit = input.single_path()?;
+        let path = resolve_path(path_lit, &input.options.base)?;
+        let module = discover::ModuleFile::new(path);
+        let modules = discover::named_submodules(&module)
+            .map_err(|error| syn::Error::new(path_lit.span(), error))?;
+        combine_module_docs(modules, path_lit.span(), &input.options)
+            .map(|docs| output(&docs))
+    }
+
+    let input = parse_macro_input!(input as Input);
+    finish(inner(&input), &input)
+}
+
+/// # Summarize the public items in a Rust source file.
+///
+/// ```ignore
+/// //! # Fruit
+/// //!
+/// //! ## Contents
+/// //!
+/// #![doc = read_doc::api_summary!("apple.rs")]
+///
+/// mod apple;
+/// pub use apple::*;
+/// ```
+///
+/// This macro generates a Markdown list of the public items in the passed file,
+/// in source order, with the first line of each item’s doc comment:
+///
+/// ```Markdown
+/// * [`Apple`]: Sweet or tart.
+/// * [`eat()`]: Eat some fruit.
+/// ```
+///
+/// Only items declared `pub` at the top level of the file are included;
+/// `pub(crate)` items, `use` declarations, and `impl` blocks are skipped.
+/// `macro_rules!` macros are included if they are `#[macro_export]`.
+///
+/// Each item is linked with an intra-doc link, so the items must be in scope
+/// where the summary is used (e.g. through `pub use apple::*`).
+///
+/// As with [`module!`], the path is relative to the directory containing the
+/// calling file. The `base` [option](module#options) is supported.
+#[proc_macro]
+pub fn api_summary(input: TokenStream) -> TokenStream {
+    fn inner(input: &Input) -> syn::Result<TokenStream> {
+        let file = parse_input_file(input)?;
+        Ok(output(&with_deprecated(
+            items::public_items(&file.items),
+            &input.options,
+            items::summary,
+        )))
+    }
+
+    let input = parse_macro_input!(input as Input);
+    finish(inner(&input), &input)
+}
+
+/// # Read the docs of every method in a trait or `impl` block.
+///
+/// ```ignore
+/// /// Extension methods for fruit.
+/// ///
+/// #[doc = read_doc::methods!("fruit.rs", item = "Fruit", shift_headings = 1)]
+/// pub trait FruitExt {
+///     // ...
+/// }
+/// ```
+///
+/// This macro finds the trait named by the `item` option in the passed file
+/// and generates an “Available methods” section containing the full docs of
+/// each of its methods, in source order:
+///
+/// ```Markdown
+/// # Available methods
+///
+/// ## `peel()`
+///
+/// Remove the peel.
+///
+/// ## `slice()`
+///
+/// Cut into pieces.
+/// ```
+///
+/// If there is no trait with that name, the public methods of inherent `impl`
+/// blocks for the type with that name (e.g. `impl Fruit { ... }`) are used
+/// instead. This is useful for keeping an extension trait’s docs in sync with
+/// the inherent methods it mirrors.
+///
+/// As with [`module!`], the path is relative to the directory containing the
+/// calling file. [Options](module#options) may be passed after the path.
+#[proc_macro]
+pub fn methods(input: TokenStream) -> TokenStream {
+    fn inner(input: &Input) -> syn::Result<TokenStream> {
+        let item = input.options.item.as_ref().ok_or_else(|| {
+            syn::Error::new(
+                Span::call_site(),
+                r#"expected an `item = "Name"` option"#,
+            )
+        })?;
+        let file = parse_input_file(input)?;
+        let methods =
+            items::methods(&file, &item.value()).ok_or_else(|| {
+                syn::Error::new(
+                    item.span(),
+                    format!("No trait or impl for {:?} found", item.value()),
+                )
+            })?;
+        let section =
+            with_deprecated(methods, &input.options, items::methods_section);
+        Ok(output(
+            &input
+                .options
+                .extractor
+                .process_docs(&section, &Directives::default()),
+        ))
+    }
+
+    let input = parse_macro_input!(input as Input);
+    finish(inner(&input), &input)
+}
+
+/// Render `items` with `render`, handling deprecated items as configured.
+///
+/// Items are deprecated if their `#[deprecated]` attribute applies to the
+/// version of the calling crate.
+fn with_deprecated<F>(
+    items: Vec<ItemDocs>,
+    options: &Options,
+    render: F,
+) -> String
+where
+    F: Fn(&[ItemDocs]) -> String,
+{
+    if options.deprecated == Deprecated::List {
+        return render(&items);
+    }
+    let version = env::var("CARGO_PKG_VERSION").unwrap_or_default();
+    let (current, deprecated) = items::partition_deprecated(items, &version);
+    if options.deprecated == Deprecated::Appendix && !deprecated.is_empty() {
+        format!(
+            "{}\n\n{}",
+            render(&current),
+            items::deprecated_section(&deprecated)
+        )
+    } else {
+        render(&current)
+    }
+}
+
+/// # Fill a Markdown template with docs from Rust source files.
+///
+/// ```ignore
+/// #![doc = read_doc::template!("overview.md")]
+/// ```
+///
+/// This macro reads a Markdown file and replaces placeholders in it with docs
+/// read from Rust source files. This lets you write the overall narrative of
+/// your documentation in one place, while keeping the details next to the code
+/// they describe.
+///
+/// ```Markdown
+/// # Fruit functionality
+///
+/// {{ include "fruit/apple.rs" }}
+///
+/// The main type is `Apple`: {{ item "fruit/apple.rs" Apple }}
+/// ```
+///
+///   * `{{ include "path" }}` is replaced with the module docs of a file, as
+///     with [`module!`]. Options for just that file may follow the path, e.g.
+///     `{{ include "path", shift_headings = 1 }}`.
+///   * `{{ item "path" Name }}` is replaced with the outer docs of the item
+///     `Name` at the top level of a file. Use `{{ item "path" name! }}` for a
+///     `macro_rules!` macro, which may also be in an inline module, and `{{
+///     item "path" impl Trait for Type }}` for an `impl` block.
+///
+/// Placeholders must be on a single line, and are ignored within code blocks.
+///
+/// The path to the template is relative to the directory containing the
+/// calling file. Paths in placeholders are relative to the directory
+/// containing the template. [Options](module#options) may be passed after the
+/// path, and apply to the docs that replace each placeholder.
+///
+/// Since the files to include, their order, and their options are all in the
+/// template, documentation can be rearranged without editing Rust code.
+#[proc_macro]
+pub fn template(input: TokenStream) -> TokenStream {
+    fn inner(input: &Input) -> syn::Result<TokenStream> {
+        let path_lit = input.single_path()?;
+        let path = resolve_path(path_lit, &input.options.base)?;
+        let error = |error| syn::Error::new(path_lit.span(), error);
+
+        let content = read_source(&path).map_err(|e| error(e.to_string()))?;
+        let dir = path.parent().unwrap_or(&path);
+        let docs = template::render(&content, |placeholder| {
+            fill_placeholder(placeholder, dir, &input.options)
         })
-        .collect::<Vec<_>>()
-        .join("\n"))
+        .map_err(error)?;
+        Ok(output(&docs))
+    }
+
+    let input = parse_macro_input!(input as Input);
+    finish(inner(&input), &input)
 }
 
-/// Get the directory containing the source file that called the macro.
+/// Get the docs to replace a template placeholder with.
 ///
 /// # Errors
 ///
-/// Returns and error if source didn’t have a path, or if we couldn’t get the
-/// parent of that path.
-fn get_source_dir() -> Result<PathBuf, syn::Error> {
-    match Span::call_site()
-        .local_file()
-        .and_then(|path| path.parent().map(Path::to_path_buf))
-    {
-        Some(path) => Ok(path),
-        None => Err(syn::Error::new(
-            Span::call_site(),
-            "Could not get path to source file",
-        )),
+/// Returns an error if an option is invalid, a file couldn’t be read or
+/// parsed, or an item wasn’t found.
+fn fill_placeholder(
+    placeholder: &template::Placeholder,
+    dir: &Path,
+    options: &Options,
+) -> Result<String, String> {
+    match placeholder {
+        template::Placeholder::Include { path, options: path_options } => {
+            let path = dir.join(path);
+            let extractor = if path_options.is_empty() {
+                Cow::Borrowed(&options.extractor)
+            } else {
+                Cow::Owned(
+                    options::apply_path_options(
+                        &options.extractor,
+                        path_options,
+                    )
+                    .map_err(|error| {
+                        format!("Invalid options for {path:?}: {error}")
+                    })?,
+                )
+            };
+            Ok(extractor
+                .extract_file(&path)
+                .map_err(|error| error.to_string())?
+                .unwrap_or_default())
+        }
+        template::Placeholder::Item { path, name } => {
+            let path = dir.join(path);
+            let file =
+                parse_source(&path).map_err(|error| error.to_string())?;
+            let docs = items::item_docs(&file, name).ok_or_else(|| {
+                ExtractError::ItemNotFound { path, name: name.clone() }
+                    .to_string()
+            })?;
+            Ok(options
+                .extractor
+                .process_docs(&docs, &Directives::default()))
+        }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use assert2::assert;
+/// # Read a Markdown file and make it safe for `rustdoc`.
+///
+/// ```ignore
+/// #![doc = read_doc::markdown!("../docs/guide.md", shift_headings = 1)]
+/// ```
+///
+/// This macro reads a plain Markdown file, such as a guide written for GitHub,
+/// and fixes the things that `rustdoc` would handle differently:
+///
+///   * Code blocks without a language are marked `text`. Otherwise `rustdoc`
+///     would treat them as Rust and run them as doctests.
+///   * Brackets like `[note]` that aren’t links are escaped. Otherwise
+///     `rustdoc` would report them as broken intra-doc links. Inline links,
+///     reference links with a definition, and brackets in code are left alone.
+///
+/// Unlike [`include_str!`], the path is relative to the directory containing
+/// the calling file, as with [`module!`]. [Options](module#options) and
+/// [directives](module#directives) are supported.
+#[proc_macro]
+pub fn markdown(input: TokenStream) -> TokenStream {
+    fn inner(input: &Input) -> syn::Result<TokenStream> {
+        let path_lit = input.single_path()?;
+        let path = resolve_path(path_lit, &input.options.base)?;
+        let error = |error| syn::Error::new(path_lit.span(), error);
 
-    #[test]
-    fn line_doc_comments() {
-        assert!(
-            extract_inner_docs(
-                r"
-//! First line
-//! Second line
+        let content = read_source(&path).map_err(error)?;
+        process_markdown(&content, &path, &input.options.extractor)
+            .map(|docs| output(&docs))
+            .map_err(error)
+    }
 
-fn foo() {}
-"
-            )
-            .unwrap()
-                == " First line\n Second line"
-        );
+    let input = parse_macro_input!(input as Input);
+    finish(inner(&input), &input)
+}
+
+/// # Read the most recent releases from a changelog.
+///
+/// ```ignore
+/// //! ## Recent changes
+/// //!
+/// #![doc = read_doc::changelog!("../CHANGELOG.md", releases = 3)]
+/// ```
+///
+/// This reads a Markdown changelog, such as one in the [Keep a Changelog]
+/// format, and keeps only the sections for releases, so that the crate docs
+/// show what changed recently without copying it by hand. With `releases = N`,
+/// only the first `N` releases are kept.
+///
+/// Release sections are those whose headings contain a version number, such
+/// as `## [1.2.0] - 2026-01-29` or `## Release 1.2.0`, at the level of the
+/// first such heading. Subsections like `### Added` are kept with their
+/// release. Everything else, such as the title and an “Unreleased” section,
+/// is dropped, except for reference definitions, which are kept so that links
+/// in release headings still work.
+///
+/// The rest is processed as with [`markdown!`], and
+/// [options](module#options) and [directives](module#directives) are
+/// supported.
+///
+/// [Keep a Changelog]: https://keepachangelog.com/
+#[proc_macro]
+pub fn changelog(input: TokenStream) -> TokenStream {
+    fn inner(input: &Input) -> syn::Result<TokenStream> {
+        let path_lit = input.single_path()?;
+        let path = resolve_path(path_lit, &input.options.base)?;
+        let error = |error| syn::Error::new(path_lit.span(), error);
+
+        let content = read_source(&path).map_err(error)?;
+        let content =
+            markdown::changelog_releases(&content, input.options.releases);
+        process_markdown(&content, &path, &input.options.extractor)
+            .map(|docs| output(&docs))
+            .map_err(error)
     }
 
-    #[test]
-    fn mixed_attrs() {
-        assert!(
-            extract_inner_docs(
-                r"
-//! First line
-#![forbid(unsafe_code)]
-//! Second line
+    let input = parse_macro_input!(input as Input);
+    finish(inner(&input), &input)
+}
+
+/// Make the contents of a Markdown file safe for `rustdoc`, then process it.
+///
+/// # Errors
+///
+/// Returns an error if the file has an invalid directive.
+fn process_markdown(
+    content: &str,
+    path: &Path,
+    extractor: &DocExtractor,
+) -> Result<String, ExtractError> {
+    let docs = markdown::escape_unresolved_links(
+        &markdown::label_plain_fences(content, "text"),
+    );
+    let (docs, directives) =
+        directives::take(&docs, extractor.audience.as_deref(), None)
+            .map_err(|message| ExtractError::invalid_docs(path, message))?;
+    if directives.skip {
+        return Ok(String::new());
+    }
+    Ok(extractor.process_docs(&docs, &directives))
+}
+
+/// # Read the docs of an example in `examples/`.
+///
+/// ```ignore
+/// #![doc = read_doc::example!("print_docs", code = true)]
+/// ```
+///
+/// This reads the module docs of `examples/print_docs.rs` (or
+/// `examples/print_docs/main.rs`) within the crate root. With `code = true`,
+/// the example’s code follows its docs in a Rust code block, without the
+/// module docs. Since the docs come straight from the example, they can’t
+/// drift from code that Cargo already builds.
+///
+/// Rust code blocks are run as doctests, which can’t use dev-dependencies.
+/// Pass `doctests = "no_run"` or `doctests = "ignore"` if the example needs
+/// them. [Options](module#options) apply to the docs, and `doctests` and
+/// `rewrite_doctests` also apply to the code.
+#[proc_macro]
+pub fn example(input: TokenStream) -> TokenStream {
+    fn inner(input: &Input) -> syn::Result<TokenStream> {
+        let name_lit = input.single_path()?;
+        let error = |error| syn::Error::new(name_lit.span(), error);
+        let name = name_lit.value();
+        let dir = get_manifest_dir()?.join("examples");
+        let path = [
+            dir.join(format!("{name}.rs")),
+            dir.join(&name).join("main.rs"),
+        ]
+        .into_iter()
+        .find(|path| path.is_file())
+        .ok_or_else(|| {
+            error(format!("Could not find example {name:?} in {dir:?}"))
+        })?;
+
+        let extractor = &input.options.extractor;
+        let docs = extractor
+            .extract_file(&path)
+            .map_err(|e| error(e.to_string()))?
+            .unwrap_or_default();
+        if !input.options.code {
+            return Ok(output(&docs));
+        }
+
+        let source = read_source(&path).map_err(|e| error(e.to_string()))?;
+        let code = process_code(extractor, &markdown::example_code(&source));
+        if docs.is_empty() {
+            Ok(output(&code))
+        } else {
+            Ok(output(&format!("{docs}\n\n{code}")))
+        }
+    }
 
-fn foo() {}
-"
+    let input = parse_macro_input!(input as Input);
+    finish(inner(&input), &input)
+}
+
+/// # Show the code of a function as an example.
+///
+/// ```ignore
+/// //! Parse a configuration file:
+/// //!
+/// #![doc = read_doc::function!("../tests/config.rs", item = "parse_file")]
+/// ```
+///
+/// This finds the function named by the `item` option in the passed file and
+/// generates a Rust code block containing the code in its body, without the
+/// signature or braces. This lets real functions, such as tests that the crate
+/// already runs, serve as examples without being copied into docs, where they
+/// would drift.
+///
+/// The first function with that name is used, even if it’s in an inline
+/// module like `mod tests` or in an `impl` block. Comments in the body are
+/// kept. With `docs = true`, the function’s own docs come before the code.
+///
+/// As with [`module!`], the path is relative to the directory containing the
+/// calling file. [Options](module#options) may be passed after the path. They
+/// apply to the docs, and `doctests` and `rewrite_doctests` also apply to the
+/// code.
+#[proc_macro]
+pub fn function(input: TokenStream) -> TokenStream {
+    fn inner(input: &Input) -> syn::Result<TokenStream> {
+        let item = input.options.item.as_ref().ok_or_else(|| {
+            syn::Error::new(
+                Span::call_site(),
+                r#"expected an `item = "name"` option"#,
             )
-            .unwrap()
-                == " First line\n Second line"
-        );
-    }
-
-    #[test]
-    fn block_doc_comments() {
-        assert!(
-            extract_inner_docs(
-                r"
-/*! Block doc comment
-with multiple lines
-*/
-
-fn foo() {}
-"
+        })?;
+        let path_lit = input.single_path()?;
+        let path = resolve_path(path_lit, &input.options.base)?;
+        let error = |error: String| syn::Error::new(path_lit.span(), error);
+        let source = read_source(&path).map_err(|e| error(e.to_string()))?;
+        let not_found = || {
+            syn::Error::new(
+                item.span(),
+                format!("No function named {:?} found", item.value()),
             )
-            .unwrap()
-                == " Block doc comment\nwith multiple lines\n"
-        );
+        };
+        let extractor = &input.options.extractor;
+        let code = items::function_code(&source, &item.value())
+            .ok_or_else(not_found)?;
+        let code = process_code(extractor, &markdown::rust_code_block(&code));
+        if !input.options.docs {
+            return Ok(output(&code));
+        }
+
+        let file =
+            syn::parse_file(&source).map_err(|e| error(e.to_string()))?;
+        let docs = items::function_docs(&file.items, &item.value())
+            .ok_or_else(not_found)?;
+        let docs = extractor.process_docs(&docs, &Directives::default());
+        if docs.trim().is_empty() {
+            Ok(output(&code))
+        } else {
+            Ok(output(&format!("{docs}\n\n{code}")))
+        }
     }
 
-    #[test]
-    fn doc_attributes() {
-        assert!(
-            extract_inner_docs(
-                r#"
-#![doc = "First line"]
-#![doc = "Second line"]
+    let input = parse_macro_input!(input as Input);
+    finish(inner(&input), &input)
+}
 
-fn foo() {}
-"#
-            )
-            .unwrap()
-                == "First line\nSecond line"
-        );
+/// Apply the `rewrite_doctests` and `doctests` options to a code block.
+fn process_code(extractor: &DocExtractor, code: &str) -> String {
+    let code = markdown::rewrite_doctests(code, &extractor.rewrite_doctests);
+    match extractor.doctests.attribute() {
+        Some(attribute) => markdown::set_doctest_attribute(&code, attribute),
+        None => code,
     }
+}
 
-    #[test]
-    fn mixed_doc_styles() {
-        assert!(
-            extract_inner_docs(
-                r#"
-//! Line comment
-#![doc = "Attribute doc"]
+/// Read and parse the single file passed to a macro.
+///
+/// # Errors
+///
+/// Returns an error if there wasn’t exactly one path, or if the file couldn’t
+/// be read or parsed.
+fn parse_input_file(input: &Input) -> syn::Result<syn::File> {
+    let path_lit = input.single_path()?;
+    let path = resolve_path(path_lit, &input.options.base)?;
+    parse_source(&path).map_err(|error| syn::Error::new(path_lit.span(), error))
+}
 
-fn foo() {}
-"#
-            )
-            .unwrap()
-                == " Line comment\nAttribute doc"
-        );
+/// Read docs from module files and combine them, separated by blank lines.
+///
+/// `modules` are paired with their paths relative to the calling module, e.g.
+/// `trunk::branch`. If `link_headings` is set, each module’s first heading is
+/// linked to the module. If `link_to_module` is set, a link to each module is
+/// added after its docs. If `nest_headings` is set, each module’s headings are
+/// shifted by how deeply it’s nested.
+///
+/// # Errors
+///
+/// Returns an error at `span` for every file that couldn’t be read or parsed.
+fn combine_module_docs(
+    modules: Vec<(String, discover::ModuleFile)>,
+    span: Span,
+    options: &Options,
+) -> syn::Result<String> {
+    let files = modules
+        .into_iter()
+        .map(|(path, module)| (module.path, span, path));
+    let docs: Vec<String> = collect_docs(files, &options.extractor)?
+        .into_iter()
+        .map(|(path, mut docs)| {
+            if options.nest_headings {
+                docs =
+                    markdown::shift_headings(&docs, path.matches("::").count());
+            }
+            if options.link_headings {
+                docs =
+                    markdown::link_first_heading(&docs, &format!("mod@{path}"));
+            }
+            if options.link_to_module {
+                let space = if options.extractor.summary {
+                    " "
+                } else {
+                    "\n\n"
+                };
+                docs = format!(
+                    "{docs}{space}See [`{path}`](mod@{path}) for details."
+                );
+            }
+            docs
+        })
+        .collect();
+    Ok(docs.join(options.file_separator()))
+}
+
+/// Check that glob matches are within the crate’s source directories.
+///
+/// This catches globs that accidentally match build output or other files that
+/// weren’t meant to be included. Globs starting with `$WORKSPACE/` are checked
+/// against the workspace’s source directories instead of the crate’s. It’s
+/// skipped for `base = out_dir`, since files generated by build scripts are
+/// never in the source directories.
+///
+/// # Errors
+///
+/// Returns an error for the first match outside of the source directories,
+/// unless `allow_outside = true` was passed.
+fn check_glob_matches(
+    matches: &[PathBuf],
+    pattern: &str,
+    span: Span,
+    options: &Options,
+) -> syn::Result<()> {
+    if options.allow_outside || matches!(options.base, Base::OutDir(_)) {
+        return Ok(());
+    }
+    let crate_dir = if pattern.starts_with(WORKSPACE_PREFIX) {
+        get_workspace_dir()?
+    } else {
+        get_manifest_dir()?
+    };
+    match matches
+        .iter()
+        .find(|path| discover::is_outside_source_dirs(&crate_dir, path))
+    {
+        Some(path) => Err(syn::Error::new(
+            span,
+            format!(
+                "Glob {pattern:?} matched {path:?}, which is outside of \
+                {}; use `allow_outside = true` to include it",
+                discover::SOURCE_DIRS
+                    .map(|dir| format!("{dir}/"))
+                    .join(", "),
+            ),
+        )),
+        None => Ok(()),
     }
+}
 
-    #[test]
-    fn no_docs() {
-        assert!(extract_inner_docs("fn foo() {}\n").unwrap() == "");
+/// Check that every submodule declared in the calling file with docs is in
+/// `files`.
+///
+/// # Errors
+///
+/// Returns an error for every submodule with docs that isn’t included, or if
+/// the calling file couldn’t be found or parsed.
+fn check_submodules<T>(
+    files: &[(PathBuf, Span, T)],
+    source_file: Option<&Path>,
+    options: &Options,
+) -> syn::Result<()> {
+    let error = |message: String| syn::Error::new(Span::call_site(), message);
+    let source_file = source_file.ok_or_else(|| {
+        error(
+            "`require_submodules` needs the location of the calling file, \
+            which is unavailable"
+                .to_owned(),
+        )
+    })?;
+    let included: Vec<PathBuf> = files
+        .iter()
+        .filter_map(|(path, _, _)| path.canonicalize().ok())
+        .collect();
+    let module = discover::ModuleFile::new(source_file.to_owned());
+    let errors = discover::named_submodules(&module)
+        .map_err(error)?
+        .into_iter()
+        .filter(|(_, module)| {
+            module
+                .path
+                .canonicalize()
+                .is_ok_and(|path| !included.contains(&path))
+        })
+        .filter_map(|(name, module)| {
+            match options.extractor.extract_file(&module.path) {
+                Ok(None) => None,
+                Ok(Some(_)) => Some(error(format!(
+                    "Submodule `{name}` has docs, but they aren’t included"
+                ))),
+                Err(extract_error) => Some(error(extract_error.to_string())),
+            }
+        });
+    match errors.reduce(|mut all, error| {
+        all.combine(error);
+        all
+    }) {
+        Some(errors) => Err(errors),
+        None => Ok(()),
     }
+}
 
-    #[test]
-    fn only_outer_docs_ignored() {
-        assert!(
-            extract_inner_docs(
-                r"
-/// This is an outer doc comment
-fn foo() {}
-"
-            )
-            .unwrap()
-                == ""
-        );
+/// Read and process the docs from files with `extractor`, keeping a tag with
+/// each file’s docs.
+///
+/// Files without docs are left out.
+///
+/// # Errors
+///
+/// Returns an error for every file that couldn’t be read or parsed.
+fn collect_docs<I, T>(
+    files: I,
+    extractor: &DocExtractor,
+) -> syn::Result<Vec<(T, String)>>
+where
+    I: IntoIterator<Item = (PathBuf, Span, T)>,
+{
+    let mut paths = Vec::new();
+    let mut tags = Vec::new();
+    for (path, span, tag) in files {
+        paths.push(path);
+        tags.push((span, tag));
     }
+    let mut docs = Vec::new();
+    let mut errors: Option<syn::Error> = None;
+    for (result, (span, tag)) in
+        extractor.extract_files(&paths).into_iter().zip(tags)
+    {
+        match result {
+            Ok(Some(file_docs)) => docs.push((tag, file_docs)),
+            Ok(None) => {}
+            Err(error) => {
+                let error = syn::Error::new(span, error);
+                match &mut errors {
+                    Some(errors) => errors.combine(error),
+                    None => errors = Some(error),
+                }
+            }
+        }
+    }
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(docs),
+    }
+}
 
-    #[test]
-    fn realistic_module() {
-        assert!(
-            extract_inner_docs(
-                r"
-//! # Module Title
-//!
-//! This module does things.
+/// Produce a string literal containing `docs`.
+fn output(docs: &str) -> TokenStream {
+    let lit = LitStr::new(docs, Span::call_site());
+    quote! { #lit }.into()
+}
 
-use std::io;
+/// Convert the result of a function-like macro into its output.
+///
+/// If there are several errors, they are wrapped in a block so that the output
+/// is still a single expression.
+fn finish(result: syn::Result<TokenStream>, input: &Input) -> TokenStream {
+    track_read_paths();
+    match result {
+        Ok(stream) => stream,
+        Err(error) => {
+            let error = add_base_dir_note(error, input);
+            if error.clone().into_iter().nth(1).is_none() {
+                error.to_compile_error().into()
+            } else {
+                let errors = error.into_iter().map(|e| e.to_compile_error());
+                quote! { { #(#errors)* "" } }.into()
+            }
+        }
+    }
+}
 
-/// Function doc
-pub fn do_thing() {}
-"
-            )
-            .unwrap()
-                == " # Module Title\n\n This module does things."
-        );
+/// Tell the compiler about the files read by this macro call.
+///
+/// This makes the compiler rebuild the calling crate when one of the files
+/// changes, even if it’s not a module of the crate, e.g. a Markdown file. It
+/// requires nightly Rust and `--cfg read_doc_unstable`; otherwise, the paths
+/// are just discarded.
+fn track_read_paths() {
+    let paths = take_read_paths();
+    #[cfg(read_doc_unstable)]
+    paths.into_iter().for_each(proc_macro::tracked::path);
+    #[cfg(not(read_doc_unstable))]
+    drop(paths);
+}
+
+/// Add an explanation to `error` if paths were resolved using a fallback.
+fn add_base_dir_note(mut error: syn::Error, input: &Input) -> syn::Error {
+    if let Some(note) = base_dir_note(&input.options.base) {
+        error.combine(syn::Error::new(Span::call_site(), note));
     }
+    error
+}
 
-    #[test]
-    fn empty_doc_lines() {
-        assert!(
-            extract_inner_docs(
-                r"
-//! First
-//!
-//! Third
+/// Prefix for paths relative to the root of the calling crate.
+const CRATE_PREFIX: &str = "$CRATE/";
 
-fn foo() {}
-"
-            )
-            .unwrap()
-                == " First\n\n Third"
-        );
+/// Prefix for paths relative to the root of the calling crate’s workspace.
+const WORKSPACE_PREFIX: &str = "$WORKSPACE/";
+
+/// Resolve a path passed to a macro.
+///
+/// # Errors
+///
+/// Returns an error if the directory the path is relative to couldn’t be
+/// determined.
+fn resolve_path(path_lit: &LitStr, base: &Base) -> syn::Result<PathBuf> {
+    let path = path_lit.value();
+    let (dir, rest) = split_path_prefix(&path, base)?;
+    Ok(dir.join(rest))
+}
+
+/// Split a `$CRATE/` or `$WORKSPACE/` prefix off of `path`.
+///
+/// Returns the directory that the rest of the path is relative to, and the
+/// rest of the path. Paths without a prefix are relative to `base`.
+///
+/// # Errors
+///
+/// Returns an error if the directory couldn’t be determined.
+fn split_path_prefix<'a>(
+    path: &'a str,
+    base: &Base,
+) -> syn::Result<(PathBuf, &'a str)> {
+    if let Some(rest) = path.strip_prefix(CRATE_PREFIX) {
+        Ok((get_manifest_dir()?, rest))
+    } else if let Some(rest) = path.strip_prefix(WORKSPACE_PREFIX) {
+        Ok((get_workspace_dir()?, rest))
+    } else {
+        Ok((get_base_dir(base)?, path))
+    }
+}
+
+/// Get the directory that paths are relative to.
+///
+/// If the calling file’s location is unavailable (e.g. in some IDEs), paths are
+/// resolved relative to `CARGO_MANIFEST_DIR` instead.
+///
+/// # Errors
+///
+/// Returns an error if the directory couldn’t be determined.
+fn get_base_dir(base: &Base) -> syn::Result<PathBuf> {
+    match base {
+        Base::SourceDir => get_source_dir().map_or_else(get_manifest_dir, Ok),
+        Base::ManifestDir => get_manifest_dir(),
+        Base::OutDir(span) => {
+            env::var_os("OUT_DIR").map(PathBuf::from).ok_or_else(|| {
+                syn::Error::new(
+                    *span,
+                    "OUT_DIR is not set; does this crate have a build script?",
+                )
+            })
+        }
+    }
+}
+
+/// Explain how paths were resolved if [`get_base_dir()`] used a fallback.
+fn base_dir_note(base: &Base) -> Option<String> {
+    if matches!(base, Base::SourceDir) && get_source_dir().is_none() {
+        Some(
+            "Paths are relative to CARGO_MANIFEST_DIR because the location of \
+            the calling file is unavailable; use `base = manifest_dir` to \
+            resolve paths the same way everywhere"
+                .to_owned(),
+        )
+    } else {
+        None
     }
 }
+
+/// Get the root directory of the calling crate’s workspace.
+///
+/// This is the closest directory, starting with `CARGO_MANIFEST_DIR`, with a
+/// `Cargo.toml` that has a `[workspace]` table. If there isn’t one, the crate
+/// isn’t part of a workspace, so `CARGO_MANIFEST_DIR` is returned.
+///
+/// # Errors
+///
+/// Returns an error if `CARGO_MANIFEST_DIR` isn’t set.
+fn get_workspace_dir() -> syn::Result<PathBuf> {
+    /// Does `dir` contain a `Cargo.toml` with a `[workspace]` table?
+    fn is_workspace_root(dir: &Path) -> bool {
+        fs::read_to_string(dir.join("Cargo.toml")).is_ok_and(|manifest| {
+            manifest.lines().map(str::trim).any(|line| {
+                line == "[workspace]" || line.starts_with("[workspace.")
+            })
+        })
+    }
+
+    let manifest_dir = get_manifest_dir()?;
+    Ok(manifest_dir
+        .ancestors()
+        .find(|dir| is_workspace_root(dir))
+        .unwrap_or(&manifest_dir)
+        .to_path_buf())
+}
+
+/// Get the directory containing the source file that called the macro.
+///
+/// Returns `None` if the source didn’t have a path, or if we couldn’t get the
+/// parent of that path.
+fn get_source_dir() -> Option<PathBuf> {
+    Span::call_site()
+        .local_file()
+        .and_then(|path| path.parent().map(Path::to_path_buf))
+}
+
+/// Get the root directory of the crate being compiled.
+///
+/// # Errors
+///
+/// Returns an error if `CARGO_MANIFEST_DIR` isn’t set, i.e. the crate isn’t
+/// being built by Cargo.
+fn get_manifest_dir() -> syn::Result<PathBuf> {
+    env::var_os("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .ok_or_else(|| {
+            syn::Error::new(
+                Span::call_site(),
+                "Could not get path to source file, and CARGO_MANIFEST_DIR is \
+                not set",
+            )
+        })
+}
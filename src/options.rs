@@ -0,0 +1,1244 @@
+//! Parse macro input: paths and `name = value` options.
+
+use std::ops::Range;
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::quote;
+use read_doc_core::assets::AssetUrls;
+use read_doc_core::owners::Owners;
+use read_doc_core::{
+    Blocks, DocExtractor, DocStyle, Doctests, Escape, Format, Hidden, Missing,
+};
+use syn::parse::{Parse, ParseStream, Parser};
+use syn::punctuated::Punctuated;
+use syn::{
+    Attribute, Ident, LitBool, LitInt, LitStr, RangeLimits, Token, braced,
+    bracketed, parenthesized, token,
+};
+
+/// Input for the function-like macros.
+pub struct Input {
+    /// Paths to the files, relative to the directory of the calling file.
+    pub paths: Vec<PathArg>,
+
+    /// Options that control how docs are processed.
+    pub options: Options,
+}
+
+impl Input {
+    /// Get the single path passed to a macro that only takes one path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there wasn’t exactly one path.
+    pub fn single_path(&self) -> syn::Result<&LitStr> {
+        self.ungated()?;
+        match self.paths.as_slice() {
+            [path] if path.extractor.is_some() => Err(syn::Error::new(
+                path.path.span(),
+                "options for a single path are only supported by `module!`, \
+                `toc!`, and `#[read_doc::include]`; pass them after the path",
+            )),
+            [path] => Ok(&path.path),
+            [] => Err(syn::Error::new(Span::call_site(), "expected a path")),
+            [_, extra, ..] => Err(syn::Error::new(
+                extra.path.span(),
+                "expected only one path",
+            )),
+        }
+    }
+
+    /// Check that no paths have a `cfg` predicate.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any path has a `cfg` predicate.
+    pub fn ungated(&self) -> syn::Result<()> {
+        match self.paths.iter().find_map(|path| path.cfg.as_ref()) {
+            Some(cfg) => Err(syn::Error::new_spanned(
+                cfg,
+                "`cfg` is only supported by `#[read_doc::include]`",
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut paths: Vec<PathArg> = Vec::new();
+        let mut options = Options::default();
+        while !input.is_empty() {
+            if input.peek(Ident) && input.peek2(Token![=]) {
+                options.parse_option(input)?;
+            } else {
+                paths.push(input.parse()?);
+            }
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        for path in &mut paths {
+            path.apply_options(&options.extractor)?;
+        }
+        Ok(Self { paths, options })
+    }
+}
+
+/// A path argument, optionally gated by a `cfg` predicate or with its own
+/// options.
+///
+/// Either `"path"` or `("path", name = value, ...)`, where the options may
+/// include `cfg = predicate`. The path may also be `env!("NAME")` to read it
+/// from an environment variable.
+pub struct PathArg {
+    /// Path to the file, relative to the directory of the calling file.
+    pub path: LitStr,
+
+    /// Only include the file’s docs when this predicate is true.
+    pub cfg: Option<TokenStream>,
+
+    /// How to extract this path’s docs, if it has its own options.
+    pub extractor: Option<DocExtractor>,
+
+    /// This path’s own options other than `cfg`, not yet parsed.
+    ///
+    /// These override the macro’s options, which may come after the path, so
+    /// they’re parsed by [`PathArg::apply_options()`] once all of the input
+    /// has been parsed.
+    options: Vec<TokenStream>,
+}
+
+impl PathArg {
+    /// Set [`PathArg::extractor`] by applying this path’s own options on top
+    /// of `extractor`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an option is invalid or can’t be set for a single
+    /// path.
+    fn apply_options(&mut self, extractor: &DocExtractor) -> syn::Result<()> {
+        if self.options.is_empty() {
+            return Ok(());
+        }
+        let mut extractor = extractor.clone();
+        let mut seen = Vec::new();
+        for option in &self.options {
+            let parser = |input: ParseStream| {
+                parse_path_option(&mut extractor, &mut seen, input)
+            };
+            parser.parse2(option.clone())?;
+        }
+        self.extractor = Some(extractor);
+        Ok(())
+    }
+}
+
+/// Apply comma separated options for a single path, e.g. from a template
+/// placeholder, on top of `extractor`.
+///
+/// # Errors
+///
+/// Returns an error if an option is invalid or can’t be set for a single
+/// path.
+pub fn apply_path_options(
+    extractor: &DocExtractor,
+    options: &str,
+) -> syn::Result<DocExtractor> {
+    let mut extractor = extractor.clone();
+    let mut seen = Vec::new();
+    let parser = |input: ParseStream| {
+        while !input.is_empty() {
+            parse_path_option(&mut extractor, &mut seen, input)?;
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(())
+    };
+    parser.parse_str(options)?;
+    Ok(extractor)
+}
+
+/// Parse a single `name = value` option for a single path.
+///
+/// The first time a list option like `sections` appears in `seen`, the list
+/// inherited from the macro’s options is cleared, so that the path’s own list
+/// replaces it rather than adding to it.
+///
+/// # Errors
+///
+/// Returns an error if the option is invalid or can’t be set for a single
+/// path.
+fn parse_path_option(
+    extractor: &mut DocExtractor,
+    seen: &mut Vec<String>,
+    input: ParseStream,
+) -> syn::Result<()> {
+    let name: Ident = input.parse()?;
+    input.parse::<Token![=]>()?;
+    let name_str = name.to_string();
+    if !seen.contains(&name_str) {
+        match name_str.as_str() {
+            "exclude_sections" => extractor.exclude_sections.clear(),
+            "pre_blocks" => extractor.pre_blocks.clear(),
+            "require_sections" => extractor.require_sections.clear(),
+            "rewrite_doctests" => extractor.rewrite_doctests.clear(),
+            "sections" => extractor.sections.clear(),
+            _ => {}
+        }
+        seen.push(name_str);
+    }
+    if parse_file_option(extractor, &name, input)? {
+        Ok(())
+    } else {
+        Err(syn::Error::new(
+            name.span(),
+            format!("`{name}` can’t be set for a single path"),
+        ))
+    }
+}
+
+impl Parse for PathArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if !input.peek(token::Paren) {
+            return Ok(Self {
+                path: parse_path(input)?,
+                cfg: None,
+                extractor: None,
+                options: Vec::new(),
+            });
+        }
+
+        let content;
+        parenthesized!(content in input);
+        let path = parse_path(&content)?;
+        let mut cfg = None;
+        let mut options = Vec::new();
+        if content.is_empty() {
+            return Err(content.error("expected `cfg` or an option"));
+        }
+        while !content.is_empty() {
+            content.parse::<Token![,]>()?;
+            if content.is_empty() {
+                break;
+            }
+            let name: Ident = content.parse()?;
+            let eq: Token![=] = content.parse()?;
+            let mut value = TokenStream::new();
+            while !content.is_empty() && !content.peek(Token![,]) {
+                value.extend([content.parse::<TokenTree>()?]);
+            }
+            if name == "cfg" {
+                if value.is_empty() {
+                    return Err(content.error("expected a `cfg` predicate"));
+                }
+                cfg = Some(value);
+            } else {
+                options.push(quote! { #name #eq #value });
+            }
+        }
+        Ok(Self { path, cfg, extractor: None, options })
+    }
+}
+
+/// Parse a path: either a string literal or `env!("NAME")`.
+///
+/// `env!("NAME")` is replaced with the value of the environment variable when
+/// the macro is expanded, so that paths set by build scripts with
+/// `cargo::rustc-env` can be used.
+///
+/// # Errors
+///
+/// Returns an error if the input isn’t a path, or if the environment variable
+/// isn’t set.
+fn parse_path(input: ParseStream) -> syn::Result<LitStr> {
+    if !(input.peek(Ident) && input.peek2(Token![!])) {
+        return input.parse();
+    }
+
+    let name: Ident = input.parse()?;
+    if name != "env" {
+        return Err(syn::Error::new(name.span(), "expected `env!` or a path"));
+    }
+    input.parse::<Token![!]>()?;
+    let content;
+    parenthesized!(content in input);
+    let var: LitStr = content.parse()?;
+    if !content.is_empty() {
+        content.parse::<Token![,]>()?;
+    }
+    let value = std::env::var(var.value()).map_err(|error| {
+        syn::Error::new(
+            var.span(),
+            format!(
+                "Could not read environment variable {:?}: {error}",
+                var.value()
+            ),
+        )
+    })?;
+    Ok(LitStr::new(&value, var.span()))
+}
+
+/// An item passed to an attribute macro, split after its outer attributes.
+pub struct AttributedItem {
+    /// The item’s outer attributes, including doc comments.
+    pub attrs: Vec<Attribute>,
+
+    /// The rest of the item.
+    pub rest: TokenStream,
+}
+
+impl Parse for AttributedItem {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            attrs: input.call(Attribute::parse_outer)?,
+            rest: input.parse()?,
+        })
+    }
+}
+
+/// Options that control how docs are processed.
+#[derive(Default)]
+#[expect(
+    clippy::struct_excessive_bools,
+    reason = "each bool is an independent option"
+)]
+pub struct Options {
+    /// Options for extracting and processing docs from each file.
+    pub extractor: DocExtractor,
+
+    /// Allow globs to match files outside of the crate’s source directories.
+    pub allow_outside: bool,
+
+    /// The directory that paths are relative to.
+    pub base: Base,
+
+    /// What to do with deprecated items.
+    pub deprecated: Deprecated,
+
+    /// Include the docs of a function before its code.
+    pub docs: bool,
+
+    /// Include the code of an example after its docs.
+    pub code: bool,
+
+    /// Link the first heading of each module’s docs to the module.
+    pub link_headings: bool,
+
+    /// Add a link to each module after its docs.
+    pub link_to_module: bool,
+
+    /// Shift each module’s headings by how deeply it’s nested.
+    pub nest_headings: bool,
+
+    /// How many releases to read from a changelog, or `None` for all of them.
+    pub releases: Option<usize>,
+
+    /// Require the docs of every submodule of the calling module to be
+    /// included.
+    pub require_submodules: bool,
+
+    /// Text to add between an item’s existing docs and appended docs.
+    pub separator: String,
+
+    /// The name of the item to read docs from, for macros that read item docs.
+    pub item: Option<LitStr>,
+}
+
+impl Options {
+    /// Get the text to put between the docs of consecutive files.
+    ///
+    /// See [`DocExtractor::file_separator()`].
+    pub const fn file_separator(&self) -> &'static str {
+        self.extractor.file_separator()
+    }
+
+    /// Parse a single `name = value` option and store it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the option is unknown or its value is invalid.
+    fn parse_option(&mut self, input: ParseStream) -> syn::Result<()> {
+        let name: Ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+
+        match name.to_string().as_str() {
+            "allow_outside" => {
+                self.allow_outside = input.parse::<LitBool>()?.value;
+            }
+            "base" => self.base = input.parse()?,
+            "code" => self.code = input.parse::<LitBool>()?.value,
+            "deprecated" => self.deprecated = parse_deprecated(input)?,
+            "docs" => self.docs = input.parse::<LitBool>()?.value,
+            "item" => self.item = Some(input.parse()?),
+            "link_headings" => {
+                self.link_headings = input.parse::<LitBool>()?.value;
+            }
+            "link_to_module" => {
+                self.link_to_module = input.parse::<LitBool>()?.value;
+            }
+            "nest_headings" => {
+                self.nest_headings = input.parse::<LitBool>()?.value;
+            }
+            "releases" => {
+                self.releases = Some(input.parse::<LitInt>()?.base10_parse()?);
+            }
+            "require_submodules" => {
+                self.require_submodules = input.parse::<LitBool>()?.value;
+            }
+            "separator" => self.separator = input.parse::<LitStr>()?.value(),
+            _ => {
+                if !parse_file_option(&mut self.extractor, &name, input)? {
+                    return Err(syn::Error::new(
+                        name.span(),
+                        format!("unknown option `{name}`"),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parse the value of an option that controls how each file’s docs are read
+/// and processed, and store it in `extractor`.
+///
+/// These options may also be passed for a single path.
+///
+/// Returns `false` without parsing anything if `name` isn’t such an option.
+///
+/// # Errors
+///
+/// Returns an error if the value is invalid.
+fn parse_file_option(
+    extractor: &mut DocExtractor,
+    name: &Ident,
+    input: ParseStream,
+) -> syn::Result<bool> {
+    match name.to_string().as_str() {
+        "anywhere" => extractor.anywhere = input.parse::<LitBool>()?.value,
+        "audience" => {
+            extractor.audience = Some(input.parse::<LitStr>()?.value());
+        }
+        "between" => {
+            let content;
+            parenthesized!(content in input);
+            let start: LitStr = content.parse()?;
+            content.parse::<Token![,]>()?;
+            let end: LitStr = content.parse()?;
+            if !content.is_empty() {
+                content.parse::<Token![,]>()?;
+            }
+            extractor.between = Some((start.value(), end.value()));
+        }
+        "blocks" => extractor.blocks = parse_blocks(input)?,
+        "collapse_blank_lines" => {
+            extractor.collapse_blank_lines = input.parse::<LitBool>()?.value;
+        }
+        "doctests" => extractor.doctests = parse_doctests(input)?,
+        "escape" => extractor.escape = parse_escape(input)?,
+        "exclude_sections" => {
+            extractor.exclude_sections.extend(parse_strings(input)?);
+        }
+        "format" => extractor.format = parse_format(input)?,
+        "heading_suffix" => {
+            extractor.heading_suffix = input.parse::<LitStr>()?.value();
+        }
+        "hidden" => extractor.hidden = parse_hidden(input)?,
+        "lenient" => {
+            extractor.lenient = input.parse::<LitBool>()?.value;
+            check_lenient_module(extractor, name)?;
+        }
+        "lines" => extractor.lines = Some(parse_lines(input)?),
+        "link_items" => extractor.link_items = input.parse::<LitBool>()?.value,
+        "missing" => extractor.missing = parse_missing(input)?,
+        "module" => {
+            extractor.module = Some(input.parse::<LitStr>()?.value());
+            check_lenient_module(extractor, name)?;
+        }
+        "owners" => extractor.owners = Some(parse_owners(input)?),
+        "pre_blocks" => extractor.pre_blocks.extend(parse_strings(input)?),
+        "prefix" => extractor.prefix = input.parse::<LitStr>()?.value(),
+        "raw" => extractor.raw = input.parse::<LitBool>()?.value,
+        "require_sections" => {
+            extractor.require_sections.extend(parse_strings(input)?);
+        }
+        "rewrite_assets" => {
+            extractor.rewrite_assets = Some(parse_asset_urls(input)?);
+        }
+        "rewrite_doctests" => {
+            let content;
+            braced!(content in input);
+            extractor.rewrite_doctests.extend(
+                Punctuated::<Pair, Token![,]>::parse_terminated(&content)?
+                    .into_iter()
+                    .map(|pair| (pair.from.value(), pair.to.value())),
+            );
+        }
+        "sections" => extractor.sections.extend(parse_strings(input)?),
+        "shift_headings" => {
+            extractor.shift_headings =
+                input.parse::<LitInt>()?.base10_parse()?;
+        }
+        "strip_license" => {
+            extractor.strip_license = if input.peek(token::Bracket) {
+                Some(parse_strings(input)?)
+            } else if input.parse::<LitBool>()?.value {
+                Some(Vec::new())
+            } else {
+                None
+            };
+        }
+        "styles" => extractor.styles = parse_styles(input)?,
+        "summary" => extractor.summary = input.parse::<LitBool>()?.value,
+        "suffix" => extractor.suffix = input.parse::<LitStr>()?.value(),
+        "trim" => extractor.trim = input.parse::<LitBool>()?.value,
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+/// Check that `lenient` and `module` aren’t both set.
+///
+/// A file must be parsed to find an inline module in it, so `lenient` would
+/// have no effect.
+///
+/// # Errors
+///
+/// Returns an error at `name`, the option that was just set, if both are set.
+fn check_lenient_module(
+    extractor: &DocExtractor,
+    name: &Ident,
+) -> syn::Result<()> {
+    if extractor.lenient && extractor.module.is_some() {
+        Err(syn::Error::new(
+            name.span(),
+            "`lenient` isn’t supported with `module`, since the file must be \
+            parsed to find the module",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// What to do with deprecated items in lists of items.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Deprecated {
+    /// List them with other items.
+    #[default]
+    List,
+
+    /// List them in a separate “Deprecated” section at the end.
+    Appendix,
+
+    /// Leave them out.
+    Skip,
+}
+
+/// The directory that paths are relative to.
+#[derive(Debug, Default)]
+#[expect(
+    clippy::enum_variant_names,
+    reason = "variants match the option values"
+)]
+pub enum Base {
+    /// The directory containing the calling source file.
+    #[default]
+    SourceDir,
+
+    /// `CARGO_MANIFEST_DIR`, the root directory of the crate.
+    ManifestDir,
+
+    /// `OUT_DIR`, where build scripts write generated files.
+    OutDir(Span),
+}
+
+impl Parse for Base {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident: Ident = input.parse()?;
+        match ident.to_string().as_str() {
+            "source_dir" => Ok(Self::SourceDir),
+            "manifest_dir" => Ok(Self::ManifestDir),
+            "out_dir" => Ok(Self::OutDir(ident.span())),
+            _ => Err(syn::Error::new(
+                ident.span(),
+                "expected `source_dir`, `manifest_dir`, or `out_dir`",
+            )),
+        }
+    }
+}
+
+/// Parse a list of strings, e.g. `["a", "b"]`.
+///
+/// # Errors
+///
+/// Returns an error if the input isn’t a bracketed list of string literals.
+fn parse_strings(input: ParseStream) -> syn::Result<Vec<String>> {
+    let content;
+    bracketed!(content in input);
+    Ok(Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?
+        .into_iter()
+        .map(|lit| lit.value())
+        .collect())
+}
+
+/// Parse the value of the `styles` option, e.g. `["line", "block"]`.
+///
+/// # Errors
+///
+/// Returns an error if the value isn’t a list of `"line"`, `"block"`, and
+/// `"attr"`.
+fn parse_styles(input: ParseStream) -> syn::Result<Vec<DocStyle>> {
+    let content;
+    bracketed!(content in input);
+    Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?
+        .into_iter()
+        .map(|lit| match lit.value().as_str() {
+            "line" => Ok(DocStyle::Line),
+            "block" => Ok(DocStyle::Block),
+            "attr" => Ok(DocStyle::Attribute),
+            _ => Err(syn::Error::new(
+                lit.span(),
+                r#"expected "line", "block", or "attr""#,
+            )),
+        })
+        .collect()
+}
+
+/// Parse the value of the `blocks` option.
+///
+/// # Errors
+///
+/// Returns an error if the value isn’t `all` or `first`.
+fn parse_blocks(input: ParseStream) -> syn::Result<Blocks> {
+    let ident: Ident = input.parse()?;
+    match ident.to_string().as_str() {
+        "all" => Ok(Blocks::All),
+        "first" => Ok(Blocks::First),
+        _ => Err(syn::Error::new(ident.span(), "expected `all` or `first`")),
+    }
+}
+
+/// Parse the value of the `doctests` option.
+///
+/// # Errors
+///
+/// Returns an error if the value isn’t `"test"`, `"ignore"`, or `"no_run"`.
+fn parse_doctests(input: ParseStream) -> syn::Result<Doctests> {
+    let lit: LitStr = input.parse()?;
+    match lit.value().as_str() {
+        "test" => Ok(Doctests::Test),
+        "ignore" => Ok(Doctests::Ignore),
+        "no_run" => Ok(Doctests::NoRun),
+        _ => Err(syn::Error::new(
+            lit.span(),
+            r#"expected "test", "ignore", or "no_run""#,
+        )),
+    }
+}
+
+/// Parse the value of the `escape` option.
+///
+/// # Errors
+///
+/// Returns an error if the value isn’t `"none"`, `"html"`, `"links"`, or
+/// `"all"`.
+fn parse_escape(input: ParseStream) -> syn::Result<Escape> {
+    let lit: LitStr = input.parse()?;
+    match lit.value().as_str() {
+        "none" => Ok(Escape::None),
+        "html" => Ok(Escape::Html),
+        "links" => Ok(Escape::Links),
+        "all" => Ok(Escape::All),
+        _ => Err(syn::Error::new(
+            lit.span(),
+            r#"expected "none", "html", "links", or "all""#,
+        )),
+    }
+}
+
+/// Parse the value of the `format` option.
+///
+/// # Errors
+///
+/// Returns an error if the value isn’t `"markdown"`, `"text"`, or `"ansi"`.
+fn parse_format(input: ParseStream) -> syn::Result<Format> {
+    let lit: LitStr = input.parse()?;
+    match lit.value().as_str() {
+        "markdown" => Ok(Format::Markdown),
+        "text" => Ok(Format::Text),
+        "ansi" => Ok(Format::Ansi),
+        _ => Err(syn::Error::new(
+            lit.span(),
+            r#"expected "markdown", "text", or "ansi""#,
+        )),
+    }
+}
+
+/// Parse the value of the `deprecated` option.
+///
+/// # Errors
+///
+/// Returns an error if the value isn’t `"list"`, `"appendix"`, or `"skip"`.
+fn parse_deprecated(input: ParseStream) -> syn::Result<Deprecated> {
+    let lit: LitStr = input.parse()?;
+    match lit.value().as_str() {
+        "list" => Ok(Deprecated::List),
+        "appendix" => Ok(Deprecated::Appendix),
+        "skip" => Ok(Deprecated::Skip),
+        _ => Err(syn::Error::new(
+            lit.span(),
+            r#"expected "list", "appendix", or "skip""#,
+        )),
+    }
+}
+
+/// Parse the value of the `hidden` option.
+///
+/// # Errors
+///
+/// Returns an error if the value isn’t `"include"` or `"skip"`.
+fn parse_hidden(input: ParseStream) -> syn::Result<Hidden> {
+    let lit: LitStr = input.parse()?;
+    match lit.value().as_str() {
+        "include" => Ok(Hidden::Include),
+        "skip" => Ok(Hidden::Skip),
+        _ => Err(syn::Error::new(
+            lit.span(),
+            r#"expected "include" or "skip""#,
+        )),
+    }
+}
+
+/// Parse the value of the `lines` option, e.g. `1..40` or `5..=10`.
+///
+/// Either end may be omitted. Lines are counted from 1.
+///
+/// # Errors
+///
+/// Returns an error if the value isn’t a range of line numbers.
+fn parse_lines(input: ParseStream) -> syn::Result<Range<usize>> {
+    let span = input.span();
+    let start = if input.peek(LitInt) {
+        input.parse::<LitInt>()?.base10_parse()?
+    } else {
+        1
+    };
+    let end = match input.parse::<RangeLimits>()? {
+        RangeLimits::HalfOpen(_) if !input.peek(LitInt) => usize::MAX,
+        RangeLimits::HalfOpen(_) => input.parse::<LitInt>()?.base10_parse()?,
+        RangeLimits::Closed(_) => input
+            .parse::<LitInt>()?
+            .base10_parse::<usize>()?
+            .saturating_add(1),
+    };
+    if start == 0 {
+        return Err(syn::Error::new(span, "lines are counted from 1"));
+    }
+    Ok(start..end)
+}
+
+/// Parse the value of the `missing` option.
+///
+/// # Errors
+///
+/// Returns an error if the value isn’t `"allow"` or `"error"`.
+fn parse_missing(input: ParseStream) -> syn::Result<Missing> {
+    let lit: LitStr = input.parse()?;
+    match lit.value().as_str() {
+        "allow" => Ok(Missing::Allow),
+        "error" => Ok(Missing::Error),
+        "warn" => Err(syn::Error::new(
+            lit.span(),
+            r#"proc macros can't emit warnings on stable Rust; use "error""#,
+        )),
+        _ => Err(syn::Error::new(
+            lit.span(),
+            r#"expected "allow" or "error""#,
+        )),
+    }
+}
+
+/// Parse the value of the `owners` option.
+///
+/// Patterns are relative to the root of the crate being compiled.
+///
+/// # Errors
+///
+/// Returns an error if a pattern is invalid, or if `CARGO_MANIFEST_DIR` isn’t
+/// set.
+fn parse_owners(input: ParseStream) -> syn::Result<Owners> {
+    let span = input.span();
+    let content;
+    braced!(content in input);
+    let pairs = Punctuated::<Pair, Token![,]>::parse_terminated(&content)?;
+    let root = std::env::var_os("CARGO_MANIFEST_DIR").ok_or_else(|| {
+        syn::Error::new(span, "`owners` requires CARGO_MANIFEST_DIR to be set")
+    })?;
+    Owners::new(
+        root.into(),
+        pairs
+            .into_iter()
+            .map(|pair| (pair.from.value(), pair.to.value())),
+    )
+    .map_err(|error| syn::Error::new(span, error))
+}
+
+/// Parse the value of the `rewrite_assets` option.
+///
+/// The URL serves files relative to the root of the crate being compiled.
+///
+/// # Errors
+///
+/// Returns an error if `CARGO_MANIFEST_DIR` isn’t set.
+fn parse_asset_urls(input: ParseStream) -> syn::Result<AssetUrls> {
+    let url: LitStr = input.parse()?;
+    let root = std::env::var_os("CARGO_MANIFEST_DIR").ok_or_else(|| {
+        syn::Error::new(
+            url.span(),
+            "`rewrite_assets` requires CARGO_MANIFEST_DIR to be set",
+        )
+    })?;
+    Ok(AssetUrls::new(root.into(), &url.value()))
+}
+
+/// A `"from" => "to"` pair in `rewrite_doctests` or `owners`.
+struct Pair {
+    /// The path prefix to replace, or the glob pattern to match.
+    from: LitStr,
+
+    /// The replacement, or the owner of matching files.
+    to: LitStr,
+}
+
+impl Parse for Pair {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let from = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let to = input.parse()?;
+        Ok(Self { from, to })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert2::assert;
+
+    #[test]
+    fn paths_only() {
+        let input: Input = syn::parse_quote!("a.rs", "b.rs");
+        assert!(input.paths.len() == 2);
+        assert!(input.options.extractor.rewrite_doctests.is_empty());
+    }
+
+    #[test]
+    fn gated_path() {
+        let input: Input = syn::parse_quote! {
+            "a.rs",
+            ("b.rs", cfg = all(unix, feature = "b")),
+        };
+        assert!(input.paths.len() == 2);
+        assert!(input.paths[0].cfg.is_none());
+        assert!(
+            input.paths[1].cfg.as_ref().map(ToString::to_string)
+                == Some(r#"all (unix , feature = "b")"#.to_owned())
+        );
+        assert!(let Err(_) = input.single_path());
+    }
+
+    #[test]
+    fn path_options() {
+        let input: Input = syn::parse_quote! {
+            ("a.rs", shift_headings = 1, cfg = unix, sections = ["Usage"]),
+            "b.rs",
+            trim = true,
+        };
+        let extractor = input.paths[0].extractor.as_ref().unwrap();
+        assert!(extractor.shift_headings == 1);
+        assert!(extractor.sections == ["Usage"]);
+        assert!(extractor.trim);
+        assert!(input.paths[0].cfg.is_some());
+        assert!(input.paths[1].extractor.is_none());
+        assert!(input.options.extractor.shift_headings == 0);
+    }
+
+    #[test]
+    fn path_list_options_replace() {
+        let input: Input = syn::parse_quote! {
+            ("a.rs", sections = ["X"], sections = ["Z"]),
+            ("b.rs", trim = true),
+            sections = ["Y"],
+        };
+        let extractor = input.paths[0].extractor.as_ref().unwrap();
+        assert!(extractor.sections == ["X", "Z"]);
+        let extractor = input.paths[1].extractor.as_ref().unwrap();
+        assert!(extractor.sections == ["Y"]);
+        assert!(input.options.extractor.sections == ["Y"]);
+
+        let extractor = super::apply_path_options(
+            &input.options.extractor,
+            r#"sections = ["X"]"#,
+        )
+        .unwrap();
+        assert!(extractor.sections == ["X"]);
+    }
+
+    #[test]
+    fn invalid_path_options() {
+        let error = syn::parse_str::<Input>(r#"("a.rs", base = manifest_dir)"#)
+            .err()
+            .unwrap();
+        assert!(error.to_string() == "`base` can’t be set for a single path");
+        assert!(let Err(_) = syn::parse_str::<Input>(r#"("a.rs", trim = 1)"#));
+        let input = syn::parse_str::<Input>(r#"("a.rs", trim = true)"#);
+        assert!(let Err(_) = input.unwrap().single_path());
+    }
+
+    #[test]
+    fn apply_path_options() {
+        let extractor = DocExtractor::default();
+        let extractor = super::apply_path_options(
+            &extractor,
+            r#"shift_headings = 1, sections = ["Usage"],"#,
+        )
+        .unwrap();
+        assert!(extractor.shift_headings == 1);
+        assert!(extractor.sections == ["Usage"]);
+        let error = super::apply_path_options(&extractor, "base = out_dir")
+            .err()
+            .unwrap();
+        assert!(error.to_string() == "`base` can’t be set for a single path");
+        assert!(let Err(_) = super::apply_path_options(&extractor, "trim true"));
+    }
+
+    #[test]
+    fn env_path() {
+        let input: Input = syn::parse_quote!(env!("CARGO_PKG_NAME"), "b.rs");
+        assert!(input.paths[0].path.value() == "read-doc");
+        assert!(input.paths[1].path.value() == "b.rs");
+        let input: Input =
+            syn::parse_quote!((env!("CARGO_PKG_NAME"), cfg = unix));
+        assert!(input.paths[0].path.value() == "read-doc");
+    }
+
+    #[test]
+    fn invalid_env_path() {
+        assert!(let Err(_) = syn::parse_str::<Input>(r#"env!("READ_DOC_NONESUCH")"#));
+        assert!(let Err(_) = syn::parse_str::<Input>(r#"concat!("a.rs")"#));
+    }
+
+    #[test]
+    fn invalid_gated_path() {
+        assert!(let Err(_) = syn::parse_str::<Input>(r#"("a.rs", cfg =)"#));
+        assert!(let Err(_) = syn::parse_str::<Input>(r#"("a.rs", if = x)"#));
+    }
+
+    #[test]
+    fn rewrite_doctests() {
+        let input: Input = syn::parse_quote! {
+            "a.rs",
+            rewrite_doctests = { "crate::a" => "crate", "b" => "c" },
+        };
+        assert!(input.paths.len() == 1);
+        assert!(
+            input.options.extractor.rewrite_doctests
+                == [
+                    ("crate::a".to_owned(), "crate".to_owned()),
+                    ("b".to_owned(), "c".to_owned()),
+                ]
+        );
+    }
+
+    #[test]
+    fn allow_outside() {
+        let input: Input = syn::parse_quote!("*.rs", allow_outside = true);
+        assert!(input.options.allow_outside);
+    }
+
+    #[test]
+    fn anywhere() {
+        let input: Input = syn::parse_quote!("a.rs", anywhere = true);
+        assert!(input.options.extractor.anywhere);
+    }
+
+    #[test]
+    fn base() {
+        let input: Input = syn::parse_quote!(base = out_dir, "a.rs");
+        assert!(let Base::OutDir(_) = input.options.base);
+    }
+
+    #[test]
+    fn base_manifest_dir() {
+        let input: Input = syn::parse_quote!("a.rs", base = manifest_dir);
+        assert!(let Base::ManifestDir = input.options.base);
+    }
+
+    #[test]
+    fn invalid_base() {
+        let error = syn::parse_str::<Input>("base = nonesuch").err().unwrap();
+        assert!(
+            error.to_string()
+                == "expected `source_dir`, `manifest_dir`, or `out_dir`"
+        );
+    }
+
+    #[test]
+    fn between() {
+        let input: Input =
+            syn::parse_quote!("a.rs", between = ("<!-- a -->", "b"));
+        assert!(
+            input.options.extractor.between
+                == Some(("<!-- a -->".to_owned(), "b".to_owned()))
+        );
+    }
+
+    #[test]
+    fn blocks() {
+        let input: Input = syn::parse_quote!("a.rs", blocks = first);
+        assert!(input.options.extractor.blocks == Blocks::First);
+    }
+
+    #[test]
+    fn doctests() {
+        let input: Input = syn::parse_quote!("a.rs", doctests = "no_run");
+        assert!(input.options.extractor.doctests == Doctests::NoRun);
+    }
+
+    #[test]
+    fn invalid_doctests() {
+        let error = syn::parse_str::<Input>(r#"doctests = "run""#)
+            .err()
+            .unwrap();
+        assert!(
+            error.to_string() == r#"expected "test", "ignore", or "no_run""#
+        );
+    }
+
+    #[test]
+    fn missing() {
+        let input: Input = syn::parse_quote!("a.rs", missing = "error");
+        assert!(input.options.extractor.missing == Missing::Error);
+    }
+
+    #[test]
+    fn missing_warn() {
+        let error = syn::parse_str::<Input>(r#"missing = "warn""#)
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("warnings"));
+    }
+
+    #[test]
+    fn separator() {
+        let input: Input = syn::parse_quote!("a.rs", separator = "\n---\n");
+        assert!(input.options.separator == "\n---\n");
+    }
+
+    #[test]
+    fn shift_headings() {
+        let input: Input = syn::parse_quote!("a.rs", shift_headings = 2);
+        assert!(input.options.extractor.shift_headings == 2);
+    }
+
+    #[test]
+    fn escape() {
+        let input: Input = syn::parse_quote!("a.rs", escape = "html");
+        assert!(input.options.extractor.escape == Escape::Html);
+        let input: Input = syn::parse_quote!("a.rs", escape = "all");
+        assert!(input.options.extractor.escape == Escape::All);
+        assert!(let Err(_) = syn::parse_str::<Input>(r#""a.rs", escape = "x""#));
+    }
+
+    #[test]
+    fn sections() {
+        let input: Input =
+            syn::parse_quote!("a.rs", sections = ["A*", "!A (old)"]);
+        assert!(input.options.extractor.sections == ["A*", "!A (old)"]);
+    }
+
+    #[test]
+    fn exclude_sections() {
+        let input: Input =
+            syn::parse_quote!("a.rs", exclude_sections = ["A", "B",]);
+        assert!(input.options.extractor.exclude_sections == ["A", "B"]);
+    }
+
+    #[test]
+    fn format() {
+        let input: Input = syn::parse_quote!("a.rs", format = "text");
+        assert!(input.options.extractor.format == Format::Text);
+    }
+
+    #[test]
+    fn deprecated() {
+        let input: Input = syn::parse_quote!("a.rs", deprecated = "appendix");
+        assert!(input.options.deprecated == Deprecated::Appendix);
+    }
+
+    #[test]
+    fn hidden() {
+        let input: Input = syn::parse_quote!("a.rs", hidden = "skip");
+        assert!(input.options.extractor.hidden == Hidden::Skip);
+    }
+
+    #[test]
+    fn lenient() {
+        let input: Input = syn::parse_quote!("a.rs", lenient = true);
+        assert!(input.options.extractor.lenient);
+    }
+
+    #[test]
+    fn lenient_module() {
+        let message = "`lenient` isn’t supported with `module`, since the file \
+            must be parsed to find the module";
+        for source in [
+            r#""a.rs", lenient = true, module = "b""#,
+            r#""a.rs", module = "b", lenient = true"#,
+            r#"("a.rs", lenient = true), module = "b""#,
+        ] {
+            let error = syn::parse_str::<Input>(source).err().unwrap();
+            assert!(error.to_string() == message);
+        }
+        let input = r#""a.rs", lenient = false, module = "b""#;
+        assert!(let Ok(_) = syn::parse_str::<Input>(input));
+    }
+
+    #[test]
+    fn lines() {
+        let input: Input = syn::parse_quote!("a.rs", lines = 1..40);
+        assert!(input.options.extractor.lines == Some(1..40));
+        let input: Input = syn::parse_quote!("a.rs", lines = 5..=10);
+        assert!(input.options.extractor.lines == Some(5..11));
+        let input: Input = syn::parse_quote!("a.rs", lines = ..3);
+        assert!(input.options.extractor.lines == Some(1..3));
+        let input: Input = syn::parse_quote!("a.rs", lines = 2..);
+        assert!(input.options.extractor.lines == Some(2..usize::MAX));
+    }
+
+    #[test]
+    fn invalid_lines() {
+        let error = syn::parse_str::<Input>(r#""a.rs", lines = 0..3"#)
+            .err()
+            .unwrap();
+        assert!(error.to_string() == "lines are counted from 1");
+        assert!(let Err(_) = syn::parse_str::<Input>(r#""a.rs", lines = "1""#));
+    }
+
+    #[test]
+    fn styles() {
+        let input: Input = syn::parse_quote!("a.rs", styles = ["line", "attr"]);
+        assert!(
+            input.options.extractor.styles
+                == [DocStyle::Line, DocStyle::Attribute]
+        );
+        assert!(
+            let Err(_) = syn::parse_str::<Input>(r#""a.rs", styles = ["x"]"#)
+        );
+    }
+
+    #[test]
+    fn summary() {
+        let input: Input = syn::parse_quote!("a.rs", summary = true);
+        assert!(input.options.extractor.summary);
+    }
+
+    #[test]
+    fn module() {
+        let input: Input = syn::parse_quote!("a.rs", module = "fruit::apple");
+        assert!(
+            input.options.extractor.module.as_deref() == Some("fruit::apple")
+        );
+    }
+
+    #[test]
+    fn pre_blocks() {
+        let input: Input = syn::parse_quote!("a.rs", pre_blocks = ["mermaid"]);
+        assert!(input.options.extractor.pre_blocks == ["mermaid"]);
+    }
+
+    #[test]
+    fn raw() {
+        let input: Input = syn::parse_quote!("a.rs", raw = true);
+        assert!(input.options.extractor.raw);
+    }
+
+    #[test]
+    fn releases() {
+        let input: Input = syn::parse_quote!("a.md", releases = 3);
+        assert!(input.options.releases == Some(3));
+        assert!(let Err(_) = syn::parse_str::<Input>(r#""a.md", releases = -1"#));
+    }
+
+    #[test]
+    fn item() {
+        let input: Input = syn::parse_quote!("a.rs", item = "Apple");
+        assert!(input.options.item.unwrap().value() == "Apple");
+    }
+
+    #[test]
+    fn owners() {
+        let input: Input = syn::parse_quote! {
+            "a.rs",
+            owners = { "tests/tree/**" => "roots", "src/*" => "core" },
+        };
+        let owners = input.options.extractor.owners.unwrap();
+        assert!(owners.owner("tests/tree/trunk.rs".as_ref()) == Some("roots"));
+        assert!(owners.owner("tests/fruit/apple.rs".as_ref()) == None);
+    }
+
+    #[test]
+    fn invalid_owners() {
+        let error = syn::parse_str::<Input>(r#"owners = { "[" => "a" }"#)
+            .err()
+            .unwrap();
+        assert!(error.to_string().starts_with(r#"Invalid glob "[""#));
+    }
+
+    #[test]
+    fn prefix_suffix() {
+        let input: Input =
+            syn::parse_quote!("a.rs", prefix = "> ", suffix = "\n\n---");
+        assert!(input.options.extractor.prefix == "> ");
+        assert!(input.options.extractor.suffix == "\n\n---");
+    }
+
+    #[test]
+    fn strip_license() {
+        let input: Input = syn::parse_quote!("a.rs", strip_license = true);
+        assert!(input.options.extractor.strip_license == Some(vec![]));
+        let input: Input = syn::parse_quote!("a.rs", strip_license = false);
+        assert!(input.options.extractor.strip_license == None);
+        let input: Input = syn::parse_quote!("a.rs", strip_license = ["Foo"]);
+        assert!(
+            input.options.extractor.strip_license
+                == Some(vec!["Foo".to_owned()])
+        );
+    }
+
+    #[test]
+    fn trim() {
+        let input: Input = syn::parse_quote!("a.rs", trim = true);
+        assert!(input.options.extractor.trim);
+    }
+
+    #[test]
+    fn attributed_item() {
+        let item: AttributedItem = syn::parse_quote! {
+            /// Docs.
+            #[derive(Debug)]
+            pub struct Apple;
+        };
+        assert!(item.attrs.len() == 2);
+        assert!(item.rest.to_string() == "pub struct Apple ;");
+    }
+
+    #[test]
+    fn unknown_option() {
+        let error = syn::parse_str::<Input>(r#""a.rs", nonesuch = 1"#)
+            .err()
+            .unwrap();
+        assert!(error.to_string() == "unknown option `nonesuch`");
+    }
+}
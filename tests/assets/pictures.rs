@@ -0,0 +1,6 @@
+//! ![Diagram](diagram.svg)
+//!
+//! See [the diagram](./diagram.svg#top), [`Vec`](std::vec::Vec),
+//! [missing](missing.png), and [the site](https://example.com/a.png).
+//!
+//! [diagram]: ../assets/diagram.svg "Diagram"
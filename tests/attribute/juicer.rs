@@ -0,0 +1,10 @@
+//! Juice extraction.
+
+/// Internal helpers.
+mod helpers {
+    /// Squeeze juice from fruit.
+    #[macro_export]
+    macro_rules! juice {
+        () => {};
+    }
+}
@@ -0,0 +1,40 @@
+//! # Attribute
+//!
+//! Testing adding docs to an item with an attribute.
+
+/// A basket of fruit.
+#[read_doc::include("../fruit/apple.rs", shift_headings = 1)]
+#[derive(Clone, Copy, Debug)]
+pub struct Basket;
+
+/// A wrapper around an apple.
+#[read_doc::doc_from("../fruit/apple.rs", item = "Apple")]
+#[derive(Clone, Copy, Debug)]
+pub struct AppleWrapper;
+
+/// Fruit for some platforms.
+#[read_doc::include(
+    "../fruit/apple.rs",
+    ("../blocks/notes.rs", cfg = unix),
+    ("../escape/html.rs", cfg = not(unix)),
+)]
+#[derive(Clone, Copy, Debug)]
+pub struct PlatformBasket;
+
+/// A wrapper around a macro.
+#[read_doc::doc_from("juicer.rs", item = "juice!")]
+#[derive(Clone, Copy, Debug)]
+pub struct JuiceWrapper;
+
+/// Fruit, separated from its notes.
+#[read_doc::include("../blocks/notes.rs", separator = "\n---\n")]
+#[derive(Clone, Copy, Debug)]
+pub struct SeparatedBasket;
+
+/// A box of peaches.
+#[read_doc::doc_from("../methods/peach.rs", item = "impl PeachExt for Box")]
+#[derive(Clone, Copy, Debug)]
+pub struct PeachBox;
+
+#[read_doc::doc_from("../fruit/apple.rs")]
+pub use crate::fruit::Apple as ReexportedApple;
@@ -0,0 +1,12 @@
+//! <!-- intro-start -->
+//! ### Apple processing
+//!
+//! Green or red, we don't care.
+//! <!-- intro-end -->
+//!
+//! #### Internal details
+//!
+//! These should not be included.
+
+/// Sweet or tart.
+pub struct Apple;
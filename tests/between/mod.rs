@@ -0,0 +1,11 @@
+//! # Between markers
+//!
+//! Testing including only part of a file’s docs.
+
+#![doc = read_doc::module!(
+    "apple.rs",
+    between = ("<!-- intro-start -->", "<!-- intro-end -->"),
+)]
+
+mod apple;
+pub use apple::*;
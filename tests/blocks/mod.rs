@@ -0,0 +1,8 @@
+//! # Blocks
+//!
+//! Testing reading only the first block of docs.
+
+#![doc = read_doc::module!("notes.rs", blocks = first)]
+
+mod notes;
+pub use notes::*;
@@ -0,0 +1,8 @@
+//! ### Notes
+//!
+//! Public notes.
+
+//! Internal notes.
+
+/// A note.
+pub struct Notes;
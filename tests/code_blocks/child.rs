@@ -0,0 +1,14 @@
+//! ## Child with examples
+//!
+//! Refers to `crate::code_blocks::child::Child` outside of code blocks.
+//!
+//! ```
+//! use crate::code_blocks::child::Child;
+//! ```
+//!
+//! ```text
+//! crate::code_blocks::child::Child
+//! ```
+
+/// A child module type.
+pub struct Child;
@@ -0,0 +1,11 @@
+//! # Code blocks
+//!
+//! Testing transformations of code blocks.
+
+#![doc = read_doc::module!(
+    "child.rs",
+    rewrite_doctests = { "crate::code_blocks::child" => "crate::code_blocks" },
+)]
+
+mod child;
+pub use child::*;
@@ -0,0 +1,3 @@
+fn main() {
+    let _: &str = read_doc::module!("fixtures/audience_extra.rs");
+}
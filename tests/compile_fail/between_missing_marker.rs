@@ -0,0 +1,6 @@
+fn main() {
+    let _: &str = read_doc::module!(
+        "../fruit/apple.rs",
+        between = ("<!-- start -->", "<!-- end -->"),
+    );
+}
@@ -0,0 +1,11 @@
+mod fruit {
+    /// An apple.
+    pub struct Apple;
+}
+
+#[read_doc::doc_from("../fruit/apple.rs")]
+pub use fruit::*;
+
+fn main() {
+    let _ = Apple;
+}
@@ -0,0 +1,7 @@
+/// Has docs from a missing item.
+#[read_doc::doc_from("../fruit/apple.rs", item = "Nonesuch")]
+pub struct Wrapper;
+
+fn main() {
+    let _ = Wrapper;
+}
@@ -0,0 +1,3 @@
+fn main() {
+    let _: &str = read_doc::example!("nonesuch");
+}
@@ -0,0 +1,3 @@
+//! Declares a module with both `twice.rs` and `twice/mod.rs` files.
+
+mod twice;
@@ -0,0 +1 @@
+//! One source file for `twice`.
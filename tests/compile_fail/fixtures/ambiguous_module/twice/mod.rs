@@ -0,0 +1 @@
+//! The other source file for `twice`.
@@ -0,0 +1,3 @@
+//! <!-- read-doc: audience=internal, title=Internal -->
+//! Only for maintainers.
+//! <!-- read-doc: /audience -->
@@ -0,0 +1,3 @@
+//! Declares a module that doesn’t exist.
+
+mod nonesuch;
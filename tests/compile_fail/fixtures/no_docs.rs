@@ -0,0 +1,2 @@
+/// Outer docs, not module docs.
+pub struct NoDocs;
@@ -0,0 +1,3 @@
+//! Docs for a file that doesn’t parse.
+
+fn broken( {}
@@ -0,0 +1,3 @@
+fn main() {
+    let _: &str = read_doc::function!("../function/mod.rs", item = "peel");
+}
@@ -0,0 +1,7 @@
+/// Has docs from a missing file.
+#[read_doc::include("nonexistent.rs")]
+pub struct Basket;
+
+fn main() {
+    let _ = Basket;
+}
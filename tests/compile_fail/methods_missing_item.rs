@@ -0,0 +1,3 @@
+fn main() {
+    let _: &str = read_doc::methods!("../methods/peach.rs", item = "Pear");
+}
@@ -0,0 +1,3 @@
+fn main() {
+    let _: &str = read_doc::module!("fixtures/no_docs.rs", missing = "error");
+}
@@ -0,0 +1,4 @@
+fn main() {
+    let _: &str =
+        read_doc::module!("../fruit/apple.rs", owners = { "src/**" => "core" });
+}
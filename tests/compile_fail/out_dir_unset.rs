@@ -0,0 +1,3 @@
+fn main() {
+    let _: &str = read_doc::module!(base = out_dir, "generated.rs");
+}
@@ -0,0 +1,6 @@
+fn main() {
+    let _: &str = read_doc::module!(
+        "../fruit/apple.rs",
+        require_sections = ["Apple*", "Examples", "Panics"],
+    );
+}
@@ -0,0 +1,13 @@
+#[path = "require_submodules/covered.rs"]
+mod covered;
+#[path = "require_submodules/skipped.rs"]
+mod skipped;
+#[path = "require_submodules/uncovered.rs"]
+mod uncovered;
+
+fn main() {
+    let _: &str = read_doc::module!(
+        "require_submodules/covered.rs",
+        require_submodules = true,
+    );
+}
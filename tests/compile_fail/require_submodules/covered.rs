@@ -0,0 +1 @@
+//! Included in the docs.
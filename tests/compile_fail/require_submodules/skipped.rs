@@ -0,0 +1,2 @@
+//! <!-- read-doc: skip -->
+//! Intentionally left out.
@@ -0,0 +1 @@
+//! Forgotten.
@@ -0,0 +1,3 @@
+fn main() {
+    let _: &str = read_doc::template!("fixtures/missing_item.md");
+}
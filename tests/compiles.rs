@@ -1,19 +1,96 @@
 //! Ensure that the code itself compiles correctly.
 #![allow(clippy::no_effect_underscore_binding, reason = "testing")]
 
+mod attribute;
+mod between;
+mod blocks;
+mod code_blocks;
+mod directives;
 mod doc_formats;
+mod escape;
 mod fruit;
+mod function;
+mod license;
+mod markdown;
+mod methods;
+mod template;
+mod tree;
+
+#[test]
+fn attribute() {
+    let _ = attribute::Basket;
+    let _ = attribute::AppleWrapper;
+    let _ = attribute::PlatformBasket;
+    let _ = attribute::JuiceWrapper;
+    let _ = attribute::SeparatedBasket;
+    let _ = attribute::PeachBox;
+    let _ = attribute::ReexportedApple;
+}
+
+#[test]
+fn between() {
+    let _ = between::Apple;
+}
+
+#[test]
+fn blocks() {
+    let _ = blocks::Notes;
+}
+
+#[test]
+fn code_blocks() {
+    let _ = code_blocks::Child;
+}
+
+#[test]
+fn directives() {
+    let _ = directives::Hidden;
+    let _ = directives::Retitled;
+    let _ = directives::Collapsed;
+    let _ = directives::Audience;
+}
 
 #[test]
 fn doc_formats() {
     let _ = doc_formats::LineDocs;
     let _ = doc_formats::BlockDocs;
     let _ = doc_formats::AttrDocs;
+    let _ = doc_formats::MixedDocs;
     let _ = doc_formats::NoDocs;
 }
 
+#[test]
+fn escape() {
+    let _ = escape::Html;
+    let _ = escape::Links;
+}
+
 #[test]
 fn fruit() {
     let _ = fruit::Apple;
     let _ = fruit::Orange;
 }
+
+#[test]
+fn function() {
+    function::squeeze();
+}
+
+#[test]
+fn license() {
+    let _ = license::Fruit;
+}
+
+#[test]
+fn methods() {
+    use methods::PeachExt;
+    let _ = Box::new(methods::Peach { pitted: false }).is_pitted();
+}
+
+#[test]
+fn tree() {
+    let _ = tree::Roots;
+    let _ = tree::Tip;
+    let _ = tree::Trunk;
+    let _ = tree::Branch;
+}
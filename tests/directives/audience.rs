@@ -0,0 +1,12 @@
+//! ## Audience
+//!
+//! Shown to everyone.
+//!
+//! <!-- read-doc: audience=internal -->
+//! Only for maintainers.
+//! <!-- read-doc: /audience -->
+//!
+//! Also shown to everyone.
+
+/// Type in a module with an internal section.
+pub struct Audience;
@@ -0,0 +1,7 @@
+//! <!-- read-doc: collapse -->
+//! ## Collapsed
+//!
+//! Shown when expanded.
+
+/// Type in a collapsed module.
+pub struct Collapsed;
@@ -0,0 +1,7 @@
+//! <!-- read-doc: skip -->
+//! ## Hidden
+//!
+//! This should never be included.
+
+/// Type in a skipped module.
+pub struct Hidden;
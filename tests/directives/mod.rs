@@ -0,0 +1,23 @@
+//! # Directives
+//!
+//! Testing directives in included files.
+
+#![doc = read_doc::module!(
+    "hidden.rs",
+    "retitled.rs",
+    "collapsed.rs",
+    "audience.rs",
+    audience = "public",
+)]
+
+mod hidden;
+pub use hidden::*;
+
+mod retitled;
+pub use retitled::*;
+
+mod collapsed;
+pub use collapsed::*;
+
+mod audience;
+pub use audience::*;
@@ -0,0 +1,7 @@
+//! <!-- read-doc: title=New title, shift_headings=1 -->
+//! # Old title
+//!
+//! ## Section
+
+/// Type in a retitled module.
+pub struct Retitled;
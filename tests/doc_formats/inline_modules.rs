@@ -0,0 +1,11 @@
+//! # Inline modules
+
+mod fruit {
+    //! Fruit defined in this file.
+
+    mod apple {
+        //! ## Apple
+        //!
+        //! Crunchy.
+    }
+}
@@ -0,0 +1,13 @@
+//! ## Mixed-style docs
+//!
+//! Written by hand.
+
+#![doc = ""]
+#![doc = "Generated notes."]
+
+/*!
+Block notes.
+*/
+
+/// A struct with docs in every style.
+pub struct MixedDocs;
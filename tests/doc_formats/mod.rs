@@ -6,6 +6,7 @@
     "line_docs.rs",
     "block_docs.rs",
     "attr_docs.rs",
+    "mixed_docs.rs",
     "no_docs.rs",
 )]
 
@@ -18,5 +19,8 @@ pub use block_docs::*;
 mod attr_docs;
 pub use attr_docs::*;
 
+mod mixed_docs;
+pub use mixed_docs::*;
+
 mod no_docs;
 pub use no_docs::*;
@@ -0,0 +1,17 @@
+//!
+//! ## Spaced-out docs
+//!
+//!
+//! These have extra blank lines.
+//!
+//! ```text
+//! one
+//!
+//!
+//! two
+//! ```
+//!
+//!
+
+/// A struct with spaced-out docs.
+pub struct SpacedDocs;
@@ -0,0 +1,8 @@
+﻿/*!
+## Windows docs
+
+Saved with a BOM and CRLF line endings.
+*/
+
+/// A struct.
+pub struct WindowsDocs;
@@ -0,0 +1,5 @@
+//! Returns `Vec<T>` or Vec<T> & more.
+#![allow(rustdoc::invalid_html_tags, reason = "important for test")]
+
+/// Has HTML-like text in its module docs.
+pub struct Html;
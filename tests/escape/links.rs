@@ -0,0 +1,8 @@
+//! Use #[derive(Links)] on [`Links`], see [docs], or [note] <b>.
+//!
+//! [docs]: https://docs.rs
+#![allow(rustdoc::broken_intra_doc_links, reason = "important for test")]
+#![allow(rustdoc::invalid_html_tags, reason = "important for test")]
+
+/// Has brackets in its module docs.
+pub struct Links;
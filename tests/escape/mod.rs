@@ -0,0 +1,13 @@
+//! # Escaping
+//!
+//! Testing escaping special characters.
+
+#![doc = read_doc::module!("html.rs", escape = "html")]
+#![doc = ""]
+#![doc = read_doc::module!("links.rs", escape = "all")]
+
+mod html;
+pub use html::*;
+
+mod links;
+pub use links::*;
@@ -4,7 +4,8 @@
 
 #![doc = read_doc::module!(
     "apple.rs",
-    "orange.rs"
+    "orange.rs",
+    require_submodules = true,
 )]
 
 mod apple;
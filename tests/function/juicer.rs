@@ -0,0 +1,23 @@
+//! A juicer with a method and a function that share a name.
+
+/// Something that makes juice.
+pub trait Juice {
+    /// Make juice.
+    fn make() -> Self;
+}
+
+/// Squeezes oranges.
+pub struct Juicer;
+
+impl Juicer {
+    /// Make a juicer.
+    pub const fn make() -> Self {
+        // Juicers are simple.
+        Self
+    }
+}
+
+/// Make a juicer the long way.
+pub fn make() -> Juicer {
+    Juicer::make()
+}
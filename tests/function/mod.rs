@@ -0,0 +1,11 @@
+//! # Function
+//!
+//! Testing showing the code of a function.
+#![doc = read_doc::function!("mod.rs", item = "squeeze", docs = true)]
+
+/// Squeeze some juice.
+pub fn squeeze() {
+    // Oranges are juicy.
+    let juice = "orange".len();
+    assert!(juice > 1);
+}
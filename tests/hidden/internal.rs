@@ -0,0 +1,8 @@
+//! ### Internal details
+//!
+//! Implementation details that shouldn’t be documented.
+
+#![doc(hidden)]
+
+/// A detail.
+pub struct Detail;
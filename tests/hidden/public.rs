@@ -0,0 +1,6 @@
+//! ### Public interface
+//!
+//! Everything users need.
+
+/// An interface.
+pub struct Interface;
@@ -0,0 +1,13 @@
+/*!
+SPDX-License-Identifier: MIT OR Apache-2.0
+Copyright 2026 The read-doc authors. All rights reserved.
+
+All rights to these docs belong to the fruit.
+
+### Fruit processing
+
+Any fruit will do.
+*/
+
+/// A fruit.
+pub struct Fruit;
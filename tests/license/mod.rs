@@ -0,0 +1,8 @@
+//! # License headers
+//!
+//! Testing stripping license headers.
+
+#![doc = read_doc::module!("fruit.rs", strip_license = true)]
+
+mod fruit;
+pub use fruit::*;
@@ -0,0 +1,12 @@
+//! ## Basket
+//!
+//! A `Basket` holds fruit. Use `fill()` to add a `Pear`.
+
+/// Holds fruit.
+pub struct Basket;
+
+/// Add fruit to a basket.
+pub fn fill() {}
+
+/// Not public.
+struct Pear;
@@ -0,0 +1,11 @@
+//! A shelf.
+
+/// Holds fruit.
+pub struct Basket;
+
+mod shelf {
+    //! Put a `Jar` or a `Basket` on the shelf.
+
+    /// Holds jam.
+    pub struct Jar;
+}
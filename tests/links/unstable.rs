@@ -0,0 +1,8 @@
+//! An `Apple` in a file that uses unstable syntax.
+#![feature(default_field_values)]
+
+/// An apple with a default number of seeds.
+pub struct Apple {
+    /// How many seeds the apple has.
+    pub seeds: u8 = 5,
+}
@@ -0,0 +1,3 @@
+//! ## Including Markdown
+//!
+#![doc = include_str!("included.md")]
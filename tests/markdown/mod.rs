@@ -0,0 +1,3 @@
+#![doc = read_doc::markdown!("guide.md", shift_headings = 1)]
+#![doc = ""]
+#![doc = read_doc::changelog!("CHANGELOG.md", releases = 1)]
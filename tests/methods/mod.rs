@@ -0,0 +1,6 @@
+//! # Methods
+//!
+//! Testing reading method docs.
+
+mod peach;
+pub use peach::*;
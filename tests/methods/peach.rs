@@ -0,0 +1,40 @@
+//! ## Peach
+//!
+//! A peach and its extension trait.
+
+/// A fuzzy fruit.
+pub struct Peach {
+    /// Whether the pit has been removed.
+    pub pitted: bool,
+}
+
+impl Peach {
+    /// Check if the pit has been removed.
+    ///
+    /// Peaches start with pits.
+    pub const fn is_pitted(&self) -> bool {
+        self.pitted
+    }
+
+    /// Not public.
+    #[expect(dead_code, reason = "important for test")]
+    const fn private(&self) -> bool {
+        self.pitted
+    }
+}
+
+/// Extension methods for boxed peaches.
+#[doc = read_doc::methods!("peach.rs", item = "Peach", shift_headings = 1)]
+pub trait PeachExt {
+    /// Check if the pit has been removed.
+    ///
+    /// Peaches start with pits.
+    fn is_pitted(&self) -> bool;
+}
+
+/// Boxed peaches are checked through the box.
+impl PeachExt for Box<Peach> {
+    fn is_pitted(&self) -> bool {
+        self.as_ref().is_pitted()
+    }
+}
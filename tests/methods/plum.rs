@@ -0,0 +1,19 @@
+//! ## Plum
+//!
+//! A plum with some deprecated methods.
+
+/// A small fruit.
+pub struct Plum;
+
+impl Plum {
+    /// Eat the plum.
+    pub const fn eat(&self) {}
+
+    /// Squeeze the plum.
+    #[deprecated(since = "0.1.0", note = "Use `eat()`.")]
+    pub const fn squeeze(&self) {}
+
+    /// Dry the plum.
+    #[deprecated(since = "99.0.0")]
+    pub const fn dry(&self) {}
+}
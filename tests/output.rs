@@ -36,6 +36,14 @@ fn read_block_docs() {
     );
 }
 
+#[test]
+fn windows_line_endings() {
+    assert!(
+        read_doc::module!("doc_formats/windows_docs.rs")
+            == "\n## Windows docs\n\nSaved with a BOM and CRLF line endings.\n"
+    );
+}
+
 #[test]
 fn read_doc_attribute() {
     assert!(
@@ -58,3 +66,706 @@ fn read_line_docs() {
 fn read_no_docs() {
     assert!(read_doc::module!("doc_formats/no_docs.rs") == "");
 }
+
+#[test]
+fn read_recursive() {
+    assert!(
+        read_doc::recursive!("tree")
+            == " ## Roots\n\n \
+            Uses the `mod.rs` layout.\n\n \
+            ### Root tip\n\n \
+            Nested in a `mod.rs` module.\n\n \
+            ## Trunk\n\n \
+            Uses the `name.rs` layout.\n\n \
+            ### Branch\n\n \
+            Nested in a `name.rs` module."
+    );
+}
+
+#[test]
+fn read_recursive_sibling_root() {
+    assert!(
+        read_doc::recursive!("tree/trunk")
+            == " ### Branch\n\n \
+            Nested in a `name.rs` module."
+    );
+}
+
+#[test]
+fn read_submodules_mod_rs() {
+    assert!(
+        read_doc::submodules!("tree/mod.rs")
+            == " ## Roots\n\n \
+            Uses the `mod.rs` layout.\n\n \
+            ## Trunk\n\n \
+            Uses the `name.rs` layout."
+    );
+}
+
+#[test]
+fn read_submodules_name_rs() {
+    assert!(
+        read_doc::submodules!("tree/trunk.rs")
+            == " ### Branch\n\n \
+            Nested in a `name.rs` module."
+    );
+}
+
+#[test]
+fn rewrite_doctests() {
+    assert!(
+        read_doc::module!(
+            "code_blocks/child.rs",
+            rewrite_doctests = {
+                "crate::code_blocks::child" => "crate::code_blocks",
+            },
+        ) == " ## Child with examples\n\n \
+            Refers to `crate::code_blocks::child::Child` outside of code blocks.\n\n \
+            ```\n \
+            use crate::code_blocks::Child;\n \
+            ```\n\n \
+            ```text\n \
+            crate::code_blocks::child::Child\n \
+            ```"
+    );
+}
+
+#[test]
+fn doctests_ignore() {
+    assert!(
+        read_doc::module!("code_blocks/child.rs", doctests = "ignore")
+            == " ## Child with examples\n\n \
+            Refers to `crate::code_blocks::child::Child` outside of code blocks.\n\n \
+            ```ignore\n \
+            use crate::code_blocks::child::Child;\n \
+            ```\n\n \
+            ```text\n \
+            crate::code_blocks::child::Child\n \
+            ```"
+    );
+}
+
+#[test]
+fn read_glob() {
+    assert!(
+        read_doc::module!("fruit/*.rs")
+            == " ## Apple processing\n\n \
+            Green or red, we don't care.\n\n \
+            # Fruit functionality\n\n \
+            This has a lot of interesting functionality.\n\n \
+            ## Orange processing\n\n \
+            Various orange-related code."
+    );
+}
+
+#[test]
+fn api_summary() {
+    assert!(
+        read_doc::api_summary!("fruit/apple.rs")
+            == "* [`Apple`]: Sweet or tart."
+    );
+}
+
+#[test]
+fn directives() {
+    assert!(
+        read_doc::module!("directives/hidden.rs", "directives/retitled.rs")
+            == "## New title\n\n ### Section"
+    );
+    assert!(
+        read_doc::module!("directives/collapsed.rs")
+            == "<details>\n<summary>Collapsed</summary>\n\n \
+            Shown when expanded.\n\n</details>"
+    );
+}
+
+#[test]
+fn audience() {
+    assert!(
+        read_doc::module!("directives/audience.rs", audience = "public")
+            == " ## Audience\n\n Shown to everyone.\n\n \
+            Also shown to everyone."
+    );
+    assert!(
+        read_doc::module!("directives/audience.rs", audience = "internal")
+            == " ## Audience\n\n Shown to everyone.\n\n \
+            Only for maintainers.\n \
+            Also shown to everyone."
+    );
+    assert!(
+        read_doc::module!(
+            "directives/audience.rs",
+            audience = "internal",
+            lines = 1..7,
+        ) == " ## Audience\n\n Shown to everyone.\n\n Only for maintainers."
+    );
+}
+
+#[test]
+fn shift_headings() {
+    assert!(
+        read_doc::module!("fruit/apple.rs", shift_headings = 2)
+            == " #### Apple processing\n\n \
+            Green or red, we don't care."
+    );
+}
+
+#[test]
+fn methods_inherent() {
+    assert!(
+        read_doc::methods!("methods/peach.rs", item = "Peach")
+            == "# Available methods\n\n\
+            ## `is_pitted()`\n\n \
+            Check if the pit has been removed.\n\n \
+            Peaches start with pits."
+    );
+}
+
+#[test]
+fn methods_trait() {
+    assert!(
+        read_doc::methods!(
+            "methods/peach.rs",
+            item = "PeachExt",
+            shift_headings = 1,
+        ) == "## Available methods\n\n\
+            ### `is_pitted()`\n\n \
+            Check if the pit has been removed.\n\n \
+            Peaches start with pits."
+    );
+}
+
+#[test]
+fn methods_deprecated() {
+    assert!(
+        read_doc::methods!(
+            "methods/plum.rs",
+            item = "Plum",
+            deprecated = "appendix",
+        ) == "# Available methods\n\n\
+            ## `eat()`\n\n Eat the plum.\n\n\
+            ## `dry()`\n\n Dry the plum.\n\n\
+            # Deprecated\n\n\
+            * [`squeeze()`] (since 0.1.0): Use `eat()`."
+    );
+    assert!(
+        read_doc::methods!(
+            "methods/plum.rs",
+            item = "Plum",
+            deprecated = "skip"
+        ) == "# Available methods\n\n\
+            ## `eat()`\n\n Eat the plum.\n\n\
+            ## `dry()`\n\n Dry the plum."
+    );
+}
+
+#[test]
+fn escape_html() {
+    assert!(
+        read_doc::module!("doc_formats/attr_docs.rs", escape = "html")
+            == "## Attribute-style docs\n\n\
+            These use `#![doc = ...]` attributes."
+    );
+    assert!(
+        read_doc::module!("escape/html.rs", escape = "html")
+            == " Returns `Vec<T>` or Vec&lt;T&gt; &amp; more."
+    );
+}
+
+#[test]
+fn escape_links() {
+    assert!(
+        read_doc::module!("escape/links.rs", escape = "links")
+            == " Use #\\[derive(Links)\\] on [`Links`], \
+            see [docs], or \\[note\\] <b>.\n\n \
+            [docs]: https://docs.rs"
+    );
+    assert!(
+        read_doc::module!("escape/links.rs", escape = "all")
+            == " Use #\\[derive(Links)\\] on [`Links`], \
+            see [docs], or \\[note\\] &lt;b&gt;.\n\n \
+            [docs]: https://docs.rs"
+    );
+}
+
+#[test]
+fn between() {
+    assert!(
+        read_doc::module!(
+            "between/apple.rs",
+            between = ("<!-- intro-start -->", "<!-- intro-end -->"),
+        ) == " ### Apple processing\n\n Green or red, we don't care."
+    );
+}
+
+#[test]
+fn exclude_sections() {
+    assert!(
+        read_doc::module!(
+            "between/apple.rs",
+            exclude_sections = ["Internal details"],
+        ) == " <!-- intro-start -->\n ### Apple processing\n\n \
+            Green or red, we don't care.\n <!-- intro-end -->"
+    );
+}
+
+#[test]
+fn strip_license() {
+    assert!(
+        read_doc::module!("license/fruit.rs", strip_license = true)
+            == "All rights to these docs belong to the fruit.\n\n\
+            ### Fruit processing\n\nAny fruit will do.\n"
+    );
+    assert!(
+        read_doc::module!("license/fruit.rs", strip_license = ["All rights"])
+            == "### Fruit processing\n\nAny fruit will do.\n"
+    );
+}
+
+#[test]
+fn blocks_first() {
+    assert!(
+        read_doc::module!("blocks/notes.rs", blocks = first)
+            == " ### Notes\n\n Public notes."
+    );
+    assert!(
+        read_doc::module!("blocks/notes.rs")
+            == " ### Notes\n\n Public notes.\n Internal notes."
+    );
+}
+
+#[test]
+fn template() {
+    assert!(
+        read_doc::template!("template/overview.md", shift_headings = 1)
+            == "# Template\n\nTesting filling a template.\n\n \
+            ### Apple processing\n\n Green or red, we don't care.\n\n\
+            The best fruit:  Sweet or tart.\n\n\
+            ```text\n{{ not a placeholder }}\n```\n"
+    );
+}
+
+#[test]
+fn template_options() {
+    assert!(
+        read_doc::template!("template/options.md", shift_headings = 2)
+            == "### Apple processing\n\nGreen or red, we don't care.\n"
+    );
+}
+
+#[test]
+fn anywhere() {
+    assert!(
+        read_doc::module!("anywhere/generated.rs.txt", anywhere = true)
+            == " ### Generated\n\n Docs after items."
+    );
+}
+
+#[test]
+fn markdown() {
+    assert!(
+        read_doc::markdown!("markdown/guide.md", shift_headings = 1)
+            == "## Guide\n\nTick the box \\[x\\], then see the [README](README.md).\n\n\
+            ```text\n$ cargo build\n```\n"
+    );
+}
+
+#[test]
+fn changelog() {
+    let links = "[Unreleased]: https://example.com/compare/v1.1.0...main\n\
+        [1.1.0]: https://example.com/compare/v1.0.0...v1.1.0\n\
+        [1.0.0]: https://example.com/releases/v1.0.0";
+    assert!(
+        read_doc::changelog!("markdown/CHANGELOG.md", releases = 1)
+            == format!(
+                "## [1.1.0] - 2026-02-01\n\n### Added\n\n* Pears.\n\n{links}"
+            )
+    );
+    assert!(
+        read_doc::changelog!("markdown/CHANGELOG.md", shift_headings = 1)
+            == format!(
+                "### [1.1.0] - 2026-02-01\n\n#### Added\n\n* Pears.\n\n\
+                ### [1.0.0] - 2026-01-01\n\n* Apples.\n\n{links}"
+            )
+    );
+}
+
+#[test]
+fn base_manifest_dir() {
+    assert!(
+        read_doc::module!(base = manifest_dir, "tests/fruit/apple.rs")
+            == " ## Apple processing\n\n Green or red, we don't care."
+    );
+}
+
+#[test]
+fn map() {
+    assert!(
+        read_doc::map!("tree", shift_headings = 1)
+            == "* [`trunk`]: Uses the `name.rs` layout.\n\n\
+            ## `roots/`\n\n\
+            * [`roots`]: Uses the `mod.rs` layout.\n\
+            * [`roots::tip`]: Nested in a `mod.rs` module.\n\n\
+            ## `trunk/`\n\n\
+            * [`trunk::branch`]: Nested in a `name.rs` module."
+    );
+}
+
+#[test]
+fn trim() {
+    assert!(
+        read_doc::module!("doc_formats/line_docs.rs", trim = true)
+            == "## Line-style docs\n\nThese use `//!` comments."
+    );
+}
+
+#[test]
+fn raw() {
+    assert!(
+        read_doc::module!(
+            "doc_formats/line_docs.rs",
+            ("fruit/apple.rs", shift_headings = 1),
+            raw = true,
+            shift_headings = 1,
+        ) == "//! ## Line-style docs\n//!\n//! These use `//!` comments.\n\n\
+            //! ## Apple processing\n//!\n//! Green or red, we don't care."
+    );
+}
+
+#[test]
+fn toc() {
+    assert!(
+        read_doc::toc!("fruit/apple.rs", "fruit/orange.rs", "blocks/notes.rs")
+            == "* [Apple processing](#apple-processing)\n\
+            * [Orange processing](#orange-processing)\n  \
+            * [Notes](#notes)"
+    );
+}
+
+#[test]
+fn link_headings() {
+    assert!(
+        read_doc::recursive!("tree", link_headings = true)
+            == " ## [Roots](mod@roots)\n\n \
+            Uses the `mod.rs` layout.\n\n \
+            ### [Root tip](mod@roots::tip)\n\n \
+            Nested in a `mod.rs` module.\n\n \
+            ## [Trunk](mod@trunk)\n\n \
+            Uses the `name.rs` layout.\n\n \
+            ### [Branch](mod@trunk::branch)\n\n \
+            Nested in a `name.rs` module."
+    );
+    assert!(
+        read_doc::submodules!("tree/trunk.rs", link_headings = true)
+            == " ### [Branch](mod@branch)\n\n \
+            Nested in a `name.rs` module."
+    );
+}
+
+#[test]
+fn link_to_module() {
+    assert!(
+        read_doc::submodules!("tree/trunk.rs", link_to_module = true)
+            == " ### Branch\n\n \
+            Nested in a `name.rs` module.\n\n\
+            See [`branch`](mod@branch) for details."
+    );
+    assert!(
+        read_doc::recursive!("tree", summary = true, link_to_module = true)
+            == "* Uses the `mod.rs` layout. \
+            See [`roots`](mod@roots) for details.\n\
+            * Nested in a `mod.rs` module. \
+            See [`roots::tip`](mod@roots::tip) for details.\n\
+            * Uses the `name.rs` layout. \
+            See [`trunk`](mod@trunk) for details.\n\
+            * Nested in a `name.rs` module. \
+            See [`trunk::branch`](mod@trunk::branch) for details."
+    );
+}
+
+#[test]
+fn prefix_suffix() {
+    assert!(
+        read_doc::module!(
+            "fruit/apple.rs",
+            "fruit/orange.rs",
+            prefix = "From `{file}`:\n\n",
+            suffix = "\n\n---",
+        ) == "From `apple.rs`:\n\n \
+            ## Apple processing\n\n \
+            Green or red, we don't care.\n\n---\n\n\
+            From `orange.rs`:\n\n \
+            ## Orange processing\n\n \
+            Various orange-related code.\n\n---"
+    );
+}
+
+#[test]
+fn path_prefixes() {
+    let apple = " ## Apple processing\n\n Green or red, we don't care.";
+    assert!(read_doc::module!("$CRATE/tests/fruit/apple.rs") == apple);
+    assert!(read_doc::module!("$WORKSPACE/tests/fruit/a*.rs") == apple);
+}
+
+#[test]
+fn owners() {
+    assert!(
+        read_doc::module!(
+            "fruit/apple.rs",
+            "tree/trunk.rs",
+            owners = { "tests/fruit/**" => "orchard", "tests/*/*.rs" => "forest" },
+            suffix = "\n\nMaintained by {owner}.",
+        ) == " ## Apple processing\n\n \
+            Green or red, we don't care.\n\nMaintained by orchard.\n\n \
+            ## Trunk\n\n \
+            Uses the `name.rs` layout.\n\nMaintained by forest."
+    );
+}
+
+#[test]
+fn example() {
+    let docs = " # Print docs read at compile time\n\n \
+        `read_doc::module!` expands to a string literal, so it can be used\n \
+        anywhere a `&str` is expected, not just in `#![doc]` attributes.";
+    assert!(read_doc::example!("print_docs") == docs);
+    assert!(
+        read_doc::example!("print_docs", code = true, doctests = "no_run")
+            == format!(
+                "{docs}\n\n\
+                ```rust,no_run\n\
+                fn main() {{\n    \
+                println!(\"{{}}\", read_doc::module!(\"../tests/fruit/apple.rs\"));\n\
+                }}\n\
+                ```"
+            )
+    );
+}
+
+#[test]
+fn nest_headings() {
+    assert!(
+        read_doc::recursive!("tree", nest_headings = true)
+            == " ## Roots\n\n \
+            Uses the `mod.rs` layout.\n\n \
+            #### Root tip\n\n \
+            Nested in a `mod.rs` module.\n\n \
+            ## Trunk\n\n \
+            Uses the `name.rs` layout.\n\n \
+            #### Branch\n\n \
+            Nested in a `name.rs` module."
+    );
+}
+
+#[test]
+fn hidden() {
+    assert!(
+        read_doc::module!("hidden/*.rs")
+            == " ### Internal details\n\n \
+            Implementation details that shouldn’t be documented.\n\n \
+            ### Public interface\n\n \
+            Everything users need."
+    );
+    assert!(
+        read_doc::module!("hidden/*.rs", hidden = "skip")
+            == " ### Public interface\n\n Everything users need."
+    );
+}
+
+#[test]
+fn lines() {
+    assert!(
+        read_doc::module!("fruit/apple.rs", lines = 1..3)
+            == " ## Apple processing\n"
+    );
+    assert!(
+        read_doc::module!("fruit/apple.rs", lines = 3..)
+            == " Green or red, we don't care."
+    );
+    assert!(read_doc::module!("directives/hidden.rs", lines = 2..) == "");
+}
+
+#[test]
+fn script() {
+    assert!(
+        read_doc::module!("scripts/fetch.rs") == " Fetch the fruit of the day."
+    );
+    assert!(
+        read_doc::module!("scripts/fetch.rs", lenient = true)
+            == " Fetch the fruit of the day."
+    );
+}
+
+#[test]
+fn heading_suffix() {
+    assert!(
+        read_doc::module!("fruit/*.rs", heading_suffix = " ({module})")
+            == " ## Apple processing (apple)\n\n \
+            Green or red, we don't care.\n\n \
+            # Fruit functionality (fruit)\n\n \
+            This has a lot of interesting functionality.\n\n \
+            ## Orange processing (orange)\n\n \
+            Various orange-related code."
+    );
+}
+
+#[test]
+fn path_options() {
+    assert!(
+        read_doc::module!(
+            ("fruit/apple.rs", shift_headings = 1),
+            "fruit/orange.rs",
+            trim = true,
+        ) == "### Apple processing\n\nGreen or red, we don't care.\n\n\
+            ## Orange processing\n\nVarious orange-related code."
+    );
+}
+
+#[test]
+fn lenient() {
+    assert!(
+        read_doc::module!("doc_formats/unstable_syntax.rs", lenient = true)
+            == " Docs for a file that uses unstable syntax."
+    );
+}
+
+#[test]
+fn inline_module() {
+    assert!(
+        read_doc::module!(
+            "doc_formats/inline_modules.rs",
+            module = "fruit::apple"
+        ) == " ## Apple\n\n Crunchy."
+    );
+}
+
+#[test]
+fn include_str() {
+    assert!(
+        read_doc::module!("markdown/including.rs")
+            == " ## Including Markdown\n\n# Included\n\nFrom a Markdown file.\n"
+    );
+}
+
+#[test]
+fn styles() {
+    assert!(
+        read_doc::module!("doc_formats/mixed_docs.rs", styles = ["line"])
+            == " ## Mixed-style docs\n\n Written by hand."
+    );
+    assert!(
+        read_doc::module!(
+            "doc_formats/mixed_docs.rs",
+            styles = ["block", "attr"]
+        ) == "\nGenerated notes.\n\nBlock notes.\n"
+    );
+}
+
+#[test]
+fn summary() {
+    assert!(
+        read_doc::module!("fruit/*.rs", summary = true)
+            == "* Green or red, we don't care.\n\
+            * This has a lot of interesting functionality.\n\
+            * Various orange-related code."
+    );
+}
+
+#[test]
+fn format_text() {
+    assert!(
+        read_doc::module!("code_blocks/child.rs", format = "text")
+            == "Child with examples\n\n\
+            Refers to crate::code_blocks::child::Child outside of code blocks.\n\n    \
+            use crate::code_blocks::child::Child;\n\n    \
+            crate::code_blocks::child::Child"
+    );
+}
+
+#[test]
+fn rewrite_assets() {
+    assert!(
+        read_doc::module!("assets/pictures.rs", rewrite_assets = "/raw")
+            == " ![Diagram](/raw/tests/assets/diagram.svg)\n\n \
+            See [the diagram](/raw/tests/assets/diagram.svg#top), \
+            [`Vec`](std::vec::Vec),\n \
+            [missing](missing.png), and \
+            [the site](https://example.com/a.png).\n\n \
+            [diagram]: /raw/tests/assets/diagram.svg \"Diagram\""
+    );
+}
+
+#[test]
+fn collapse_blank_lines() {
+    assert!(
+        read_doc::module!(
+            "doc_formats/spaced_docs.rs",
+            "doc_formats/line_docs.rs",
+            collapse_blank_lines = true,
+        ) == " ## Spaced-out docs\n\n \
+            These have extra blank lines.\n\n \
+            ```text\n one\n\n\n two\n ```\n\n \
+            ## Line-style docs\n\n \
+            These use `//!` comments."
+    );
+}
+
+#[test]
+fn function() {
+    let code = "```rust\n\
+        // Oranges are juicy.\n\
+        let juice = \"orange\".len();\n\
+        assert!(juice > 1);\n\
+        ```";
+    assert!(read_doc::function!("function/mod.rs", item = "squeeze") == code);
+    assert!(
+        read_doc::function!(
+            "function/mod.rs",
+            item = "squeeze",
+            docs = true,
+            doctests = "ignore",
+        ) == format!(
+            " Squeeze some juice.\n\n{}",
+            code.replace("rust", "rust,ignore")
+        )
+    );
+}
+
+#[test]
+fn function_method() {
+    assert!(
+        read_doc::function!("function/juicer.rs", item = "make", docs = true)
+            == " Make a juicer.\n\n\
+            ```rust\n// Juicers are simple.\nSelf\n```"
+    );
+}
+
+#[test]
+fn link_items() {
+    assert!(
+        read_doc::module!("links/basket.rs", link_items = true)
+            == " ## Basket\n\n \
+            A [`Basket`] holds fruit. Use [`fill()`] to add a `Pear`."
+    );
+}
+
+#[test]
+fn link_items_lenient() {
+    assert!(
+        read_doc::module!(
+            "links/unstable.rs",
+            lenient = true,
+            link_items = true,
+        ) == " An `Apple` in a file that uses unstable syntax."
+    );
+}
+
+#[test]
+fn link_items_module() {
+    assert!(
+        read_doc::module!(
+            "links/shelf.rs",
+            module = "shelf",
+            link_items = true
+        ) == " Put a [`Jar`] or a `Basket` on the shelf."
+    );
+}
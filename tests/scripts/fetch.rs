@@ -0,0 +1,9 @@
+#!/usr/bin/env -S cargo +nightly -Zscript
+---
+[dependencies]
+clap = { version = "4.5", features = ["derive"] } # Don't upgrade yet.
+---
+#![cfg_attr(test, allow(unused))]
+//! Fetch the fruit of the day.
+
+fn main() {}
@@ -0,0 +1 @@
+#![doc = read_doc::template!("overview.md", shift_headings = 1)]
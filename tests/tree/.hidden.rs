@@ -0,0 +1 @@
+//! Hidden files aren’t matched by globs.
@@ -0,0 +1,11 @@
+//! # Tree
+//!
+//! Nested modules in both `mod.rs` and `name.rs` layouts.
+
+#![doc = read_doc::recursive!(".")]
+
+mod roots;
+pub use roots::*;
+
+mod trunk;
+pub use trunk::*;
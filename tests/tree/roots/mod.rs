@@ -0,0 +1,9 @@
+//! ## Roots
+//!
+//! Uses the `mod.rs` layout.
+
+mod tip;
+pub use tip::*;
+
+/// Underground.
+pub struct Roots;
@@ -0,0 +1,6 @@
+//! ### Root tip
+//!
+//! Nested in a `mod.rs` module.
+
+/// The very end.
+pub struct Tip;
@@ -0,0 +1,9 @@
+//! ## Trunk
+//!
+//! Uses the `name.rs` layout.
+
+mod branch;
+pub use branch::*;
+
+/// Above ground.
+pub struct Trunk;
@@ -0,0 +1,6 @@
+//! ### Branch
+//!
+//! Nested in a `name.rs` module.
+
+/// Leafy.
+pub struct Branch;